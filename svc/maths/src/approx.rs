@@ -0,0 +1,197 @@
+//! Tolerance-based equality for floating-point `Matrix` and `Vector` values,
+//! following the semantics of the `approx` crate.
+
+use crate::{Matrix, Storage, Vector};
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt::Debug;
+
+
+pub trait ApproxEq: Copy {
+    fn default_epsilon() -> Self;
+    fn default_max_relative() -> Self;
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self) -> bool;
+    fn relative_eq(&self, other: &Self, epsilon: Self, max_relative: Self) -> bool;
+    fn ulps_eq(&self, other: &Self, epsilon: Self, max_ulps: u32) -> bool;
+}
+
+macro_rules! impl_approx_eq {
+    ($($t:ty => $bits:ty),* $(,)?) => {
+        $(
+            impl ApproxEq for $t {
+                fn default_epsilon() -> Self {
+                    <$t>::EPSILON
+                }
+
+                fn default_max_relative() -> Self {
+                    <$t>::EPSILON
+                }
+
+                fn abs_diff_eq(&self, other: &Self, epsilon: Self) -> bool {
+                    (self - other).abs() <= epsilon
+                }
+
+                fn relative_eq(&self, other: &Self, epsilon: Self, max_relative: Self) -> bool {
+                    if self == other {
+                        return true;
+                    }
+                    if self.is_infinite() || other.is_infinite() {
+                        return false;
+                    }
+
+                    let diff = (self - other).abs();
+                    if diff <= epsilon {
+                        return true;
+                    }
+
+                    diff <= self.abs().max(other.abs()) * max_relative
+                }
+
+                fn ulps_eq(&self, other: &Self, epsilon: Self, max_ulps: u32) -> bool {
+                    if self.is_nan() || other.is_nan() {
+                        return false;
+                    }
+                    if self.abs_diff_eq(other, epsilon) {
+                        return true;
+                    }
+                    if self.is_sign_positive() != other.is_sign_positive() {
+                        return false;
+                    }
+
+                    (self.to_bits() as $bits).abs_diff(other.to_bits() as $bits) <= max_ulps as _
+                }
+            }
+        )*
+    };
+}
+
+impl_approx_eq!(f32 => i32, f64 => i64);
+
+
+fn all_eq<T>(left: &[T], right: &[T], eq: impl Fn(&T, &T) -> bool) -> bool {
+    left.len() == right.len() && left.iter().zip(right).all(|(a, b)| eq(a, b))
+}
+
+impl<T: ApproxEq, S: Storage<T>> Matrix<T, S> {
+
+    pub fn abs_diff_eq(&self, other: &Self, epsilon: T) -> bool {
+        self.rows == other.rows
+            && self.cols == other.cols
+            && all_eq(self.as_slice(), other.as_slice(), |a, b| a.abs_diff_eq(b, epsilon))
+    }
+
+    pub fn relative_eq(&self, other: &Self, epsilon: T, max_relative: T) -> bool {
+        self.rows == other.rows
+            && self.cols == other.cols
+            && all_eq(self.as_slice(), other.as_slice(), |a, b| a.relative_eq(b, epsilon, max_relative))
+    }
+
+    pub fn ulps_eq(&self, other: &Self, epsilon: T, max_ulps: u32) -> bool {
+        self.rows == other.rows
+            && self.cols == other.cols
+            && all_eq(self.as_slice(), other.as_slice(), |a, b| a.ulps_eq(b, epsilon, max_ulps))
+    }
+
+}
+
+impl<T: ApproxEq> Vector<T> {
+
+    pub fn abs_diff_eq(&self, other: &Self, epsilon: T) -> bool {
+        all_eq(&self.elements, &other.elements, |a, b| a.abs_diff_eq(b, epsilon))
+    }
+
+    pub fn relative_eq(&self, other: &Self, epsilon: T, max_relative: T) -> bool {
+        all_eq(&self.elements, &other.elements, |a, b| a.relative_eq(b, epsilon, max_relative))
+    }
+
+    pub fn ulps_eq(&self, other: &Self, epsilon: T, max_ulps: u32) -> bool {
+        all_eq(&self.elements, &other.elements, |a, b| a.ulps_eq(b, epsilon, max_ulps))
+    }
+
+}
+
+
+// lets `assert_matrix_eq!` report positions for both matrices and vectors
+#[doc(hidden)]
+pub trait Positioned<T> {
+    fn shape(&self) -> Vec<usize>;
+    fn flat(&self) -> &[T];
+    fn position(&self, idx: usize) -> String;
+}
+
+impl<T, S: Storage<T>> Positioned<T> for Matrix<T, S> {
+    fn shape(&self) -> Vec<usize> {
+        vec![self.rows, self.cols]
+    }
+
+    fn flat(&self) -> &[T] {
+        self.as_slice()
+    }
+
+    fn position(&self, idx: usize) -> String {
+        format!("({}, {})", idx / self.cols, idx % self.cols)
+    }
+}
+
+impl<T> Positioned<T> for Vector<T> {
+    fn shape(&self) -> Vec<usize> {
+        vec![self.elements.len()]
+    }
+
+    fn flat(&self) -> &[T] {
+        &self.elements
+    }
+
+    fn position(&self, idx: usize) -> String {
+        format!("[{}]", idx)
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_eq_with<A, T>(left: &A, right: &A, eq: impl Fn(&T, &T) -> bool)
+where
+    A: Positioned<T>,
+    T: Debug,
+{
+    const MAX_REPORTED: usize = 16;
+
+    let (left_shape, right_shape) = (left.shape(), right.shape());
+    if left_shape != right_shape {
+        panic!("assertion `left ≈ right` failed: shapes differ (left {:?}, right {:?})", left_shape, right_shape);
+    }
+
+    let diffs: Vec<usize> = left.flat()
+        .iter()
+        .zip(right.flat())
+        .enumerate()
+        .filter(|(_, (a, b))| !eq(a, b))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    if diffs.is_empty() {
+        return;
+    }
+
+    let mut message = format!(
+        "assertion `left ≈ right` failed: {} of {} elements differ",
+        diffs.len(),
+        left.flat().len(),
+    );
+    for &idx in diffs.iter().take(MAX_REPORTED) {
+        message += &format!(
+            "\n  {}: left = {:?}, right = {:?}",
+            left.position(idx),
+            left.flat()[idx],
+            right.flat()[idx],
+        );
+    }
+    if diffs.len() > MAX_REPORTED {
+        message += &format!("\n  ... and {} more", diffs.len() - MAX_REPORTED);
+    }
+
+    panic!("{}", message);
+}
@@ -0,0 +1,114 @@
+//! Central-difference derivatives of functions over `Vector<f64>`.
+//!
+//! The free functions use step sizes that balance truncation against
+//! rounding error; `Step` chooses one explicitly.
+
+use crate::{Matrix, Vector};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use alloc::{vec, vec::Vec};
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    /// `h * max(1, |x_i|)`, scaled to each coordinate.
+    Relative(f64),
+    Absolute(f64),
+}
+
+impl Default for Step {
+    fn default() -> Self {
+        Step::Relative(f64::EPSILON.cbrt())
+    }
+}
+
+impl Step {
+
+    fn at(self, x: f64) -> f64 {
+        match self {
+            Step::Relative(h) => h * x.abs().max(1.0),
+            Step::Absolute(h) => h,
+        }
+    }
+
+    pub fn gradient(self, f: impl Fn(&Vector<f64>) -> f64, x: &Vector<f64>) -> Vector<f64> {
+        let mut probe = x.clone();
+
+        Vector::from_fn(x.len(), |i| {
+            let h = self.at(x[i]);
+            probe[i] = x[i] + h;
+            let forward = f(&probe);
+            probe[i] = x[i] - h;
+            let backward = f(&probe);
+            probe[i] = x[i];
+            (forward - backward) / (2.0 * h)
+        })
+    }
+
+    /// One row per output of `f`, one column per coordinate of `x`.
+    pub fn jacobian(self, f: impl Fn(&Vector<f64>) -> Vector<f64>, x: &Vector<f64>) -> Matrix<f64> {
+        let mut probe = x.clone();
+        let mut columns = vec![];
+
+        for i in 0..x.len() {
+            let h = self.at(x[i]);
+            probe[i] = x[i] + h;
+            let forward = f(&probe);
+            probe[i] = x[i] - h;
+            let backward = f(&probe);
+            probe[i] = x[i];
+            columns.push(&(&forward - &backward) * (0.5 / h));
+        }
+
+        let rows = columns.first().map_or_else(|| f(x).len(), |c| c.len());
+        Matrix::from_fn(rows, x.len(), |row, col| columns[col][row])
+    }
+
+    pub fn hessian(self, f: impl Fn(&Vector<f64>) -> f64, x: &Vector<f64>) -> Matrix<f64> {
+        let n = x.len();
+        let steps: Vec<f64> = x.iter().map(|&xi| self.at(xi)).collect();
+        let centre = f(x);
+        let mut probe = x.clone();
+        let mut hessian = Matrix::new(n, n, vec![0.0; n * n]);
+
+        // f at x shifted by `di * h_i` along i and `dj * h_j` along j
+        let mut shifted = |i: usize, di: f64, j: usize, dj: f64| {
+            probe[i] += di * steps[i];
+            probe[j] += dj * steps[j];
+            let value = f(&probe);
+            probe[i] = x[i];
+            probe[j] = x[j];
+            value
+        };
+
+        for i in 0..n {
+            let h = steps[i];
+            hessian[(i, i)] = (shifted(i, 1.0, i, 0.0) - 2.0 * centre + shifted(i, -1.0, i, 0.0)) / (h * h);
+
+            for j in 0..i {
+                let value = (shifted(i, 1.0, j, 1.0) - shifted(i, 1.0, j, -1.0)
+                    - shifted(i, -1.0, j, 1.0) + shifted(i, -1.0, j, -1.0))
+                    / (4.0 * h * steps[j]);
+                hessian[(i, j)] = value;
+                hessian[(j, i)] = value;
+            }
+        }
+
+        hessian
+    }
+
+}
+
+pub fn gradient(f: impl Fn(&Vector<f64>) -> f64, x: &Vector<f64>) -> Vector<f64> {
+    Step::default().gradient(f, x)
+}
+
+pub fn jacobian(f: impl Fn(&Vector<f64>) -> Vector<f64>, x: &Vector<f64>) -> Matrix<f64> {
+    Step::default().jacobian(f, x)
+}
+
+/// Uses a larger default step than `gradient`, since second differences
+/// divide by `h²`.
+pub fn hessian(f: impl Fn(&Vector<f64>) -> f64, x: &Vector<f64>) -> Matrix<f64> {
+    Step::Relative(f64::EPSILON.powf(0.25)).hessian(f, x)
+}
@@ -0,0 +1,89 @@
+//! Float functions that live in `std` rather than `core`.
+//!
+//! With `std` these forward to the inherent methods; without it they come
+//! from `libm`. Inherent methods take precedence over the trait, so code in
+//! this crate calls `x.sqrt()` as usual and only imports `Float` in `no_std`
+//! builds. Unit tests link `std` regardless, so they skip it too:
+//!
+//! ```ignore
+//! #[cfg(not(any(feature = "std", test)))]
+//! use crate::float::Float;
+//! ```
+
+
+pub trait Float: Sized {
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tanh(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+}
+
+macro_rules! forward {
+    ($name:ident => $libm:ident $(, $arg:ident: $arg_ty:ty)*) => {
+        fn $name(self $(, $arg: $arg_ty)*) -> Self {
+            #[cfg(feature = "std")]
+            return Self::$name(self $(, $arg)*);
+            #[cfg(not(feature = "std"))]
+            return libm::$libm(self $(, $arg)*);
+        }
+    };
+}
+
+impl Float for f64 {
+    forward!(abs => fabs);
+    forward!(sqrt => sqrt);
+    forward!(cbrt => cbrt);
+    forward!(exp => exp);
+    forward!(ln => log);
+    forward!(powf => pow, n: f64);
+    forward!(sin => sin);
+    forward!(cos => cos);
+    forward!(tanh => tanh);
+    forward!(atan2 => atan2, other: f64);
+    forward!(hypot => hypot, other: f64);
+    forward!(floor => floor);
+    forward!(ceil => ceil);
+    forward!(round => round);
+
+    fn powi(self, n: i32) -> Self {
+        #[cfg(feature = "std")]
+        return f64::powi(self, n);
+        #[cfg(not(feature = "std"))]
+        return libm::pow(self, n as f64);
+    }
+}
+
+impl Float for f32 {
+    forward!(abs => fabsf);
+    forward!(sqrt => sqrtf);
+    forward!(cbrt => cbrtf);
+    forward!(exp => expf);
+    forward!(ln => logf);
+    forward!(powf => powf, n: f32);
+    forward!(sin => sinf);
+    forward!(cos => cosf);
+    forward!(tanh => tanhf);
+    forward!(atan2 => atan2f, other: f32);
+    forward!(hypot => hypotf, other: f32);
+    forward!(floor => floorf);
+    forward!(ceil => ceilf);
+    forward!(round => roundf);
+
+    fn powi(self, n: i32) -> Self {
+        #[cfg(feature = "std")]
+        return f32::powi(self, n);
+        #[cfg(not(feature = "std"))]
+        return libm::powf(self, n as f32);
+    }
+}
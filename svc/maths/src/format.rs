@@ -0,0 +1,215 @@
+use crate::{Matrix, Storage, Vector};
+use alloc::{format, string::{String, ToString}, vec, vec::Vec};
+use core::fmt;
+use core::str::FromStr;
+
+
+// like numpy: arrays with more elements than this are summarised
+const THRESHOLD: usize = 1000;
+const EDGE_ITEMS: usize = 3;
+
+// indices to print along one axis, with `None` standing in for the elided middle
+fn visible(len: usize, total: usize) -> Vec<Option<usize>> {
+    if total > THRESHOLD && len > 2 * EDGE_ITEMS {
+        (0..EDGE_ITEMS)
+            .map(Some)
+            .chain(core::iter::once(None))
+            .chain((len - EDGE_ITEMS..len).map(Some))
+            .collect()
+    } else {
+        (0..len).map(Some).collect()
+    }
+}
+
+fn element<T: fmt::Display>(f: &fmt::Formatter<'_>, x: &T) -> String {
+    match f.precision() {
+        Some(precision) => format!("{:.*}", precision, x),
+        None => x.to_string(),
+    }
+}
+
+// `None` cells are the elided middle and are written unpadded
+fn write_row(f: &mut fmt::Formatter<'_>, cells: &[Option<String>], widths: &[usize]) -> fmt::Result {
+    write!(f, "[")?;
+    for (k, (cell, &width)) in cells.iter().zip(widths).enumerate() {
+        if k > 0 {
+            write!(f, ", ")?;
+        }
+        match cell {
+            Some(cell) => write!(f, "{:>width$}", cell, width = width)?,
+            None => write!(f, "...")?,
+        }
+    }
+    write!(f, "]")
+}
+
+impl<T: fmt::Display> fmt::Display for Vector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+
+        let cells: Vec<Option<String>> = visible(self.elements.len(), self.elements.len())
+            .into_iter()
+            .map(|idx| idx.map(|i| element(f, &self.elements[i])))
+            .collect();
+
+        let width = cells.iter().flatten().map(String::len).fold(f.width().unwrap_or(0), usize::max);
+
+        write_row(f, &cells, &vec![width; cells.len()])
+    }
+}
+
+impl<T: fmt::Display, S: Storage<T>> fmt::Display for Matrix<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+
+        let total = self.as_slice().len();
+        let cols = visible(self.cols, total);
+
+        let rows: Vec<Option<Vec<Option<String>>>> = visible(self.rows, total)
+            .into_iter()
+            .map(|row| row.map(|i| {
+                cols.iter()
+                    .map(|col| col.map(|j| element(f, &self[(i, j)])))
+                    .collect()
+            }))
+            .collect();
+
+        let min_width = f.width().unwrap_or(0);
+        let widths: Vec<usize> = (0..cols.len())
+            .map(|k| {
+                rows.iter()
+                    .flatten()
+                    .filter_map(|cells| cells[k].as_ref().map(String::len))
+                    .fold(min_width, usize::max)
+            })
+            .collect();
+
+        write!(f, "[")?;
+        for (k, row) in rows.iter().enumerate() {
+            if k > 0 {
+                write!(f, ",\n ")?;
+            }
+            match row {
+                Some(cells) => write_row(f, cells, &widths)?,
+                None => write!(f, "...")?,
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+
+    fn error(&self, message: impl fmt::Display) -> ParseError {
+        ParseError {
+            message: format!("{} at offset {}", message, self.pos),
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        self.pos = self.src.len() - self.src[self.pos..].trim_start().len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        if self.src[self.pos..].starts_with(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(format_args!("expected {:?}", c)))
+        }
+    }
+
+    fn scalar<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        self.skip_ws();
+        let rest = &self.src[self.pos..];
+        let len = rest.find([',', '[', ']']).unwrap_or(rest.len());
+        let token = rest[..len].trim();
+
+        let value = token.parse().map_err(|_| self.error(format_args!("invalid element {:?}", token)))?;
+        self.pos += len;
+        Ok(value)
+    }
+
+    // a bracketed, comma separated list, allowing a trailing comma
+    fn list<U>(&mut self, mut item: impl FnMut(&mut Self) -> Result<U, ParseError>) -> Result<Vec<U>, ParseError> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        while !self.eat(']') {
+            items.push(item(self)?);
+            if !self.eat(',') {
+                self.expect(']')?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        if self.src[self.pos..].trim().is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("unexpected trailing input"))
+        }
+    }
+
+}
+
+impl<T: FromStr> FromStr for Vector<T> {
+    type Err = ParseError;
+
+    /// Parses `[1, 2, 3]`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { src: s, pos: 0 };
+        let elements = parser.list(Parser::scalar)?;
+        parser.end()?;
+
+        Ok(Vector { elements })
+    }
+}
+
+impl<T: FromStr> FromStr for Matrix<T> {
+    type Err = ParseError;
+
+    /// Parses `[[1, 2], [3, 4]]`, one inner list per row.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { src: s, pos: 0 };
+        let rows = parser.list(|p| p.list(Parser::scalar))?;
+        parser.end()?;
+
+        let cols = rows.first().map_or(0, Vec::len);
+        if let Some(i) = rows.iter().position(|row| row.len() != cols) {
+            return Err(ParseError {
+                message: format!("row {} has {} elements, expected {}", i, rows[i].len(), cols),
+            });
+        }
+
+        Ok(Matrix::new(rows.len(), cols, rows.into_iter().flatten().collect()))
+    }
+}
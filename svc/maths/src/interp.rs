@@ -0,0 +1,291 @@
+//! Interpolation of sampled data and least-squares polynomial fits.
+//!
+//! Sample positions must be strictly increasing. Queries outside the sampled
+//! range are clamped to the end values, as `np.interp` does.
+
+use crate::{Matrix, Vector};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use alloc::{vec, vec::Vec};
+use core::{cmp::Ordering, fmt};
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Error {
+    LengthMismatch {
+        xs: usize,
+        ys: usize,
+    },
+    TooFewPoints {
+        needed: usize,
+        found: usize,
+    },
+    /// The grid's shape is not `(xs.len(), ys.len())`.
+    GridMismatch {
+        axes: (usize, usize),
+        grid: (usize, usize),
+    },
+    /// `xs[index]` is not greater than the sample before it.
+    NotIncreasing {
+        index: usize,
+    },
+    /// The fit's design matrix is rank deficient, e.g. repeated sample
+    /// positions for the requested degree.
+    RankDeficient,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::LengthMismatch { xs, ys } => write!(f, "{} sample positions but {} values", xs, ys),
+            Error::TooFewPoints { needed, found } => write!(f, "need at least {} points, found {}", needed, found),
+            Error::GridMismatch { axes, grid } => write!(f, "grid of shape {:?} does not match axes of lengths {:?}", grid, axes),
+            Error::NotIncreasing { index } => write!(f, "sample positions not increasing at index {}", index),
+            Error::RankDeficient => write!(f, "fit is rank deficient"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+
+fn check_samples(xs: &[f64], ys: &[f64], needed: usize) -> Result<()> {
+    if xs.len() != ys.len() {
+        return Err(Error::LengthMismatch { xs: xs.len(), ys: ys.len() });
+    }
+    check_axis(xs, needed)
+}
+
+fn check_axis(xs: &[f64], needed: usize) -> Result<()> {
+    if xs.len() < needed {
+        return Err(Error::TooFewPoints { needed, found: xs.len() });
+    }
+    // NaN positions don't compare, so they fail too
+    match xs.windows(2).position(|w| w[1].partial_cmp(&w[0]) != Some(Ordering::Greater)) {
+        Some(idx) => Err(Error::NotIncreasing { index: idx + 1 }),
+        None => Ok(()),
+    }
+}
+
+// index `i` of the interval `xs[i]..=xs[i + 1]` holding `x`, which must be in range
+fn interval(xs: &[f64], x: f64) -> usize {
+    xs.partition_point(|&xi| xi <= x).clamp(1, xs.len() - 1) - 1
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Linear {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+}
+
+impl Linear {
+
+    pub fn new(xs: &[f64], ys: &[f64]) -> Result<Self> {
+        check_samples(xs, ys, 1)?;
+
+        Ok(Linear {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+        })
+    }
+
+    pub fn eval(&self, x: f64) -> f64 {
+        // constant, with no interval to search (which even a NaN `x` would)
+        if self.xs.len() == 1 {
+            return self.ys[0];
+        }
+
+        let (first, last) = (self.xs[0], self.xs[self.xs.len() - 1]);
+        if x <= first {
+            return self.ys[0];
+        }
+        if x >= last {
+            return self.ys[self.ys.len() - 1];
+        }
+
+        let i = interval(&self.xs, x);
+        let t = (x - self.xs[i]) / (self.xs[i + 1] - self.xs[i]);
+        self.ys[i] + t * (self.ys[i + 1] - self.ys[i])
+    }
+
+    pub fn eval_many(&self, xs: &[f64]) -> Vector<f64> {
+        xs.iter().map(|&x| self.eval(x)).collect()
+    }
+
+}
+
+
+/// Piecewise cubic with continuous first and second derivatives.
+#[derive(Debug, Clone)]
+pub struct CubicSpline {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    // second derivative at each knot
+    curvatures: Vec<f64>,
+}
+
+enum End {
+    Natural,
+    Clamped(f64, f64),
+}
+
+impl CubicSpline {
+
+    /// Zero second derivative at both ends.
+    pub fn natural(xs: &[f64], ys: &[f64]) -> Result<Self> {
+        Self::build(xs, ys, End::Natural)
+    }
+
+    /// Prescribed first derivatives at both ends.
+    pub fn clamped(xs: &[f64], ys: &[f64], start_slope: f64, end_slope: f64) -> Result<Self> {
+        Self::build(xs, ys, End::Clamped(start_slope, end_slope))
+    }
+
+    // solves the tridiagonal system for the knot curvatures with the Thomas algorithm
+    fn build(xs: &[f64], ys: &[f64], end: End) -> Result<Self> {
+        check_samples(xs, ys, 2)?;
+
+        let n = xs.len();
+        let h: Vec<f64> = xs.windows(2).map(|w| w[1] - w[0]).collect();
+        let slope = |i: usize| (ys[i + 1] - ys[i]) / h[i];
+
+        let (mut lower, mut diag, mut upper, mut rhs) = (vec![0.0; n], vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+
+        for i in 1..n - 1 {
+            lower[i] = h[i - 1];
+            diag[i] = 2.0 * (h[i - 1] + h[i]);
+            upper[i] = h[i];
+            rhs[i] = 6.0 * (slope(i) - slope(i - 1));
+        }
+
+        match end {
+            End::Natural => {
+                diag[0] = 1.0;
+                diag[n - 1] = 1.0;
+            },
+            End::Clamped(start, end) => {
+                diag[0] = 2.0 * h[0];
+                upper[0] = h[0];
+                rhs[0] = 6.0 * (slope(0) - start);
+                lower[n - 1] = h[n - 2];
+                diag[n - 1] = 2.0 * h[n - 2];
+                rhs[n - 1] = 6.0 * (end - slope(n - 2));
+            },
+        }
+
+        for i in 1..n {
+            let factor = lower[i] / diag[i - 1];
+            diag[i] -= factor * upper[i - 1];
+            rhs[i] -= factor * rhs[i - 1];
+        }
+
+        let mut curvatures = vec![0.0; n];
+        curvatures[n - 1] = rhs[n - 1] / diag[n - 1];
+        for i in (0..n - 1).rev() {
+            curvatures[i] = (rhs[i] - upper[i] * curvatures[i + 1]) / diag[i];
+        }
+
+        Ok(CubicSpline {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            curvatures,
+        })
+    }
+
+    pub fn eval(&self, x: f64) -> f64 {
+        let x = x.clamp(self.xs[0], self.xs[self.xs.len() - 1]);
+        let i = interval(&self.xs, x);
+
+        let h = self.xs[i + 1] - self.xs[i];
+        let (a, b) = ((self.xs[i + 1] - x) / h, (x - self.xs[i]) / h);
+        let (m0, m1) = (self.curvatures[i], self.curvatures[i + 1]);
+
+        a * self.ys[i] + b * self.ys[i + 1] + ((a * a * a - a) * m0 + (b * b * b - b) * m1) * h * h / 6.0
+    }
+
+    pub fn derivative(&self, x: f64) -> f64 {
+        let x = x.clamp(self.xs[0], self.xs[self.xs.len() - 1]);
+        let i = interval(&self.xs, x);
+
+        let h = self.xs[i + 1] - self.xs[i];
+        let (a, b) = ((self.xs[i + 1] - x) / h, (x - self.xs[i]) / h);
+        let (m0, m1) = (self.curvatures[i], self.curvatures[i + 1]);
+
+        (self.ys[i + 1] - self.ys[i]) / h - (3.0 * a * a - 1.0) * h * m0 / 6.0 + (3.0 * b * b - 1.0) * h * m1 / 6.0
+    }
+
+    pub fn eval_many(&self, xs: &[f64]) -> Vector<f64> {
+        xs.iter().map(|&x| self.eval(x)).collect()
+    }
+
+}
+
+
+/// Least-squares polynomial fit of the given degree, solved with QR on the
+/// Vandermonde matrix. Coefficients are in ascending powers, `c[0] + c[1] x + ...`.
+pub fn polyfit(xs: &[f64], ys: &[f64], degree: usize) -> Result<Vector<f64>> {
+    if xs.len() != ys.len() {
+        return Err(Error::LengthMismatch { xs: xs.len(), ys: ys.len() });
+    }
+    if xs.len() <= degree {
+        return Err(Error::TooFewPoints { needed: degree + 1, found: xs.len() });
+    }
+
+    let vandermonde = Matrix::from_fn(xs.len(), degree + 1, |row, col| xs[row].powi(col as i32));
+
+    vandermonde
+        .least_squares(&Vector::new(ys.to_vec()))
+        .ok_or(Error::RankDeficient)
+}
+
+/// Evaluates ascending-power coefficients with Horner's scheme.
+pub fn polyval(coefficients: &Vector<f64>, x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, &c| acc * x + c)
+}
+
+
+/// Bilinear interpolation on a rectilinear grid, `values[(i, j)]` being the
+/// sample at `(xs[i], ys[j])`.
+#[derive(Debug, Clone)]
+pub struct Bilinear {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    values: Matrix<f64>,
+}
+
+impl Bilinear {
+
+    pub fn new(xs: &[f64], ys: &[f64], values: &Matrix<f64>) -> Result<Self> {
+        check_axis(xs, 2)?;
+        check_axis(ys, 2)?;
+        if (values.rows, values.cols) != (xs.len(), ys.len()) {
+            return Err(Error::GridMismatch {
+                axes: (xs.len(), ys.len()),
+                grid: (values.rows, values.cols),
+            });
+        }
+
+        Ok(Bilinear {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            values: values.clone(),
+        })
+    }
+
+    pub fn eval(&self, x: f64, y: f64) -> f64 {
+        let x = x.clamp(self.xs[0], self.xs[self.xs.len() - 1]);
+        let y = y.clamp(self.ys[0], self.ys[self.ys.len() - 1]);
+        let (i, j) = (interval(&self.xs, x), interval(&self.ys, y));
+
+        let tx = (x - self.xs[i]) / (self.xs[i + 1] - self.xs[i]);
+        let ty = (y - self.ys[j]) / (self.ys[j + 1] - self.ys[j]);
+        let v = |di: usize, dj: usize| self.values[(i + di, j + dj)];
+
+        (1.0 - tx) * ((1.0 - ty) * v(0, 0) + ty * v(0, 1)) + tx * ((1.0 - ty) * v(1, 0) + ty * v(1, 1))
+    }
+
+}
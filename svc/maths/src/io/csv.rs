@@ -0,0 +1,202 @@
+//! Delimited text tables of `f64`, one matrix row per record.
+
+use super::{Error, Result};
+use crate::Matrix;
+use std::prelude::v1::*;
+use std::{format, vec};
+use std::io::{BufRead, Write};
+
+
+/// What to do with empty fields and fields matching `CsvOptions::na_values`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Missing {
+    #[default]
+    Error,
+    Fill(f64),
+    SkipRow,
+}
+
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    pub has_header: bool,
+    pub missing: Missing,
+    pub na_values: Vec<String>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: b',',
+            has_header: false,
+            missing: Missing::Error,
+            na_values: vec!["NA".to_string(), "N/A".to_string()],
+        }
+    }
+}
+
+// splits one record, honouring double-quoted fields with `""` escapes
+fn split_record(line: &str, delimiter: char, line_no: usize) -> Result<Vec<String>> {
+
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            },
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            },
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(Error::Parse {
+            line: line_no,
+            message: "unterminated quoted field".to_string(),
+        });
+    }
+
+    fields.push(field);
+    Ok(fields)
+}
+
+// the reader works line by line, so fields can't span lines
+fn quote_field(field: &str, delimiter: char) -> Result<String> {
+    if field.contains(['\n', '\r']) {
+        return Err(Error::Unsupported(format!("line break in csv field {:?}", field)));
+    }
+
+    if field.contains(delimiter) || field.contains('"') {
+        Ok(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Ok(field.to_string())
+    }
+}
+
+impl Matrix<f64> {
+
+    /// Parses a table of numbers, returning the matrix and the header row if
+    /// `options.has_header` is set.
+    pub fn from_csv_reader<R: BufRead>(
+        reader: R,
+        options: &CsvOptions,
+    ) -> Result<(Matrix<f64>, Option<Vec<String>>)> {
+
+        let delimiter = options.delimiter as char;
+        let mut header = None;
+        let mut cols = None;
+        let mut rows = 0;
+        let mut elements = Vec::new();
+
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line_no = idx + 1;
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let fields = split_record(line, delimiter, line_no)?;
+
+            if options.has_header && header.is_none() {
+                cols = Some(fields.len());
+                header = Some(fields.into_iter().map(|f| f.trim().to_string()).collect());
+                continue;
+            }
+
+            match cols {
+                Some(n) if n != fields.len() => return Err(Error::Parse {
+                    line: line_no,
+                    message: format!("expected {} fields, found {}", n, fields.len()),
+                }),
+                None => cols = Some(fields.len()),
+                _ => {},
+            }
+
+            let mut row = Vec::with_capacity(fields.len());
+            let mut skip = false;
+
+            for (col, field) in fields.iter().enumerate() {
+                let field = field.trim();
+
+                if field.is_empty() || options.na_values.iter().any(|na| na == field) {
+                    match options.missing {
+                        Missing::Error => return Err(Error::Parse {
+                            line: line_no,
+                            message: format!("missing value in column {}", col + 1),
+                        }),
+                        Missing::Fill(value) => row.push(value),
+                        Missing::SkipRow => skip = true,
+                    }
+                    continue;
+                }
+
+                let value = field.parse().map_err(|_| Error::Parse {
+                    line: line_no,
+                    message: format!("invalid number {:?} in column {}", field, col + 1),
+                })?;
+                row.push(value);
+            }
+
+            if !skip {
+                elements.extend(row);
+                rows += 1;
+            }
+        }
+
+        let cols = if rows == 0 { 0 } else { cols.unwrap_or(0) };
+
+        Ok((Matrix::new(rows, cols, elements), header))
+    }
+
+    /// Writes one record per row, preceded by `header` if given. Rows without
+    /// columns would be blank lines, which reading skips, so matrices with
+    /// rows but no columns are refused.
+    pub fn to_csv_writer<W: Write>(
+        &self,
+        mut writer: W,
+        options: &CsvOptions,
+        header: Option<&[&str]>,
+    ) -> Result<()> {
+
+        if self.cols == 0 && self.rows > 0 {
+            return Err(Error::Unsupported(format!("CSV of a {} × 0 matrix", self.rows)));
+        }
+
+        let delimiter = options.delimiter as char;
+
+        if let Some(header) = header {
+            if header.len() != self.cols {
+                return Err(Error::ShapeMismatch {
+                    expected: format!("{} header fields", self.cols),
+                    found: vec![header.len()],
+                });
+            }
+
+            let fields = header.iter().map(|h| quote_field(h, delimiter)).collect::<Result<Vec<_>>>()?;
+            writeln!(writer, "{}", fields.join(&delimiter.to_string()))?;
+        }
+
+        for row in 0..self.rows {
+            let fields: Vec<String> = (0..self.cols)
+                .map(|col| self[(row, col)].to_string())
+                .collect();
+            writeln!(writer, "{}", fields.join(&delimiter.to_string()))?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+}
@@ -0,0 +1,212 @@
+//! Read-only memory-mapped matrices, for datasets larger than memory.
+//!
+//! A file is a 32-byte header followed by the elements, row-major and
+//! little-endian:
+//!
+//! | bytes    | contents                               |
+//! |----------|----------------------------------------|
+//! | `0..8`   | magic `MMATRIX` and format version `1` |
+//! | `8`      | element kind, `f` or `i`               |
+//! | `9`      | element size in bytes                  |
+//! | `10..16` | zero                                   |
+//! | `16..24` | rows, `u64`                            |
+//! | `24..32` | columns, `u64`                         |
+//!
+//! Pages are read in as rows are touched, so only the rows in use need to
+//! fit in memory.
+
+use super::npy::Element;
+use super::{Error, Result};
+use crate::{simd, Matrix, Vector};
+use memmap2::Mmap;
+use std::prelude::v1::*;
+use std::format;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Range};
+use std::path::Path;
+
+
+const MAGIC: &[u8; 8] = b"MMATRIX\x01";
+const HEADER_LEN: usize = 32;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+    impl Sealed for i32 {}
+    impl Sealed for i64 {}
+}
+
+/// Element types that can be viewed in place: plain numbers with no
+/// invalid bit patterns.
+pub trait Mappable: Element + sealed::Sealed {}
+
+impl Mappable for f32 {}
+impl Mappable for f64 {}
+impl Mappable for i32 {}
+impl Mappable for i64 {}
+
+fn dtype(kind: u8, size: u8) -> String {
+    format!("{}{}", kind as char, size)
+}
+
+
+pub struct MappedMatrix<T> {
+    pub rows: usize,
+    pub cols: usize,
+    map: Mmap,
+    element: PhantomData<T>,
+}
+
+impl<T: Mappable> MappedMatrix<T> {
+
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the map is only read; as with any file mapping, another
+        // process truncating or rewriting the file while it is mapped is
+        // undefined behaviour
+        let map = unsafe { Mmap::map(&file)? };
+
+        Self::from_map(map)
+    }
+
+    fn from_map(map: Mmap) -> Result<Self> {
+
+        if cfg!(target_endian = "big") {
+            return Err(Error::Unsupported("mapping little-endian data on a big-endian host".to_string()));
+        }
+
+        if map.len() < HEADER_LEN || &map[..8] != MAGIC {
+            return Err(Error::Format("not a mapped matrix file".to_string()));
+        }
+
+        let (kind, size) = (map[8], map[9]);
+        if kind as char != T::KIND || size as usize != T::SIZE {
+            return Err(Error::DtypeMismatch {
+                expected: dtype(T::KIND as u8, T::SIZE as u8),
+                found: dtype(kind, size),
+            });
+        }
+
+        let rows = u64::from_le_bytes(map[16..24].try_into().unwrap()) as usize;
+        let cols = u64::from_le_bytes(map[24..32].try_into().unwrap()) as usize;
+
+        let data_len = rows
+            .checked_mul(cols)
+            .and_then(|n| n.checked_mul(T::SIZE))
+            .ok_or_else(|| Error::Format("shape overflows".to_string()))?;
+        if map.len() - HEADER_LEN != data_len {
+            return Err(Error::ShapeMismatch {
+                expected: format!("{} bytes of data", data_len),
+                found: vec![rows, cols],
+            });
+        }
+
+        Ok(MappedMatrix {
+            rows,
+            cols,
+            map,
+            element: PhantomData,
+        })
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        let data = &self.map[HEADER_LEN..];
+        debug_assert_eq!(data.as_ptr() as usize % std::mem::align_of::<T>(), 0);
+
+        // SAFETY: `Mappable` types are plain numbers valid for any bits, the
+        // length was checked on open, and the page-aligned map plus the
+        // 32-byte header keeps elements aligned
+        unsafe { std::slice::from_raw_parts(data.as_ptr().cast::<T>(), self.rows * self.cols) }
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.rows, "Row {} out of bounds for {} rows.", row, self.rows);
+        &self.as_slice()[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// The elements of rows `range`, contiguous and row-major.
+    pub fn row_range(&self, range: Range<usize>) -> &[T] {
+        assert!(range.start <= range.end && range.end <= self.rows, "Rows {:?} out of bounds for {} rows.", range, self.rows);
+        &self.as_slice()[range.start * self.cols..range.end * self.cols]
+    }
+
+    /// Rows `range` as a matrix borrowing the mapped elements.
+    pub fn view(&self, range: Range<usize>) -> Matrix<T, &[T]> {
+        Matrix::new(range.len(), self.cols, self.row_range(range))
+    }
+
+    /// Copies rows `range` into an owned matrix.
+    pub fn batch(&self, range: Range<usize>) -> Matrix<T> {
+        self.view(range).to_owned()
+    }
+
+    /// Consecutive views of up to `batch_size` rows.
+    pub fn batches(&self, batch_size: usize) -> impl Iterator<Item = Matrix<T, &[T]>> + '_ {
+        assert!(batch_size > 0, "Batch size must be positive.");
+
+        (0..self.rows)
+            .step_by(batch_size)
+            .map(move |start| self.view(start..(start + batch_size).min(self.rows)))
+    }
+
+    /// Copies the whole matrix into memory.
+    pub fn to_matrix(&self) -> Matrix<T> {
+        self.batch(0..self.rows)
+    }
+
+}
+
+impl<T> Mul<&Vector<T>> for &MappedMatrix<T>
+where
+    T: Mappable + Mul<Output = T> + Add<Output = T> + 'static,
+{
+    type Output = Vector<T>;
+
+    fn mul(self, vector: &Vector<T>) -> Vector<T> {
+        assert_eq!(self.cols, vector.len(), "Matrix columns must match vector size");
+
+        (0..self.rows)
+            .map(|row| {
+                let row = self.row(row);
+                simd::dot(row, vector).unwrap_or_else(|| simd::scalar::dot(row, vector))
+            })
+            .collect()
+    }
+}
+
+
+/// Writes the header for a `rows × cols` matrix of `T`; the elements must
+/// follow as `rows * cols` little-endian values, e.g. streamed row by row.
+pub fn write_header<T: Mappable, W: Write>(mut writer: W, rows: usize, cols: usize) -> Result<()> {
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    header.extend_from_slice(MAGIC);
+    header.push(T::KIND as u8);
+    header.push(T::SIZE as u8);
+    header.extend_from_slice(&[0; 6]);
+    header.extend_from_slice(&(rows as u64).to_le_bytes());
+    header.extend_from_slice(&(cols as u64).to_le_bytes());
+
+    writer.write_all(&header)?;
+    Ok(())
+}
+
+pub fn write_matrix<T: Mappable, W: Write>(mut writer: W, matrix: &Matrix<T>) -> Result<()> {
+    write_header::<T, _>(&mut writer, matrix.rows, matrix.cols)?;
+
+    let mut data = Vec::with_capacity(matrix.elements.len() * T::SIZE);
+    for &x in &matrix.elements {
+        x.extend_le_bytes(&mut data);
+    }
+    writer.write_all(&data)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+pub fn save_matrix<T: Mappable>(path: impl AsRef<Path>, matrix: &Matrix<T>) -> Result<()> {
+    write_matrix(BufWriter::new(File::create(path)?), matrix)
+}
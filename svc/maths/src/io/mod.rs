@@ -0,0 +1,67 @@
+//! Reading and writing `Matrix`/`Vector` data in external file formats.
+
+pub mod csv;
+pub mod mapped;
+pub mod mtx;
+pub mod npy;
+
+mod zip;
+
+use std::prelude::v1::*;
+use std::fmt;
+
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    Format(String),
+    Parse {
+        line: usize,
+        message: String,
+    },
+    Unsupported(String),
+    DtypeMismatch {
+        expected: String,
+        found: String,
+    },
+    ShapeMismatch {
+        expected: String,
+        found: Vec<usize>,
+    },
+    MissingArray(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "i/o error: {}", err),
+            Error::Format(msg) => write!(f, "malformed input: {}", msg),
+            Error::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            Error::Unsupported(msg) => write!(f, "unsupported: {}", msg),
+            Error::DtypeMismatch { expected, found } => {
+                write!(f, "dtype mismatch: expected {}, found {}", expected, found)
+            },
+            Error::ShapeMismatch { expected, found } => {
+                write!(f, "shape mismatch: expected {}, found {:?}", expected, found)
+            },
+            Error::MissingArray(name) => write!(f, "no array named {:?}", name),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
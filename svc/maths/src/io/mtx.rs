@@ -0,0 +1,225 @@
+//! Matrix Market (`.mtx`) exchange files, in dense `array` and sparse
+//! `coordinate` form.
+//!
+//! `real`, `integer` and `pattern` fields are read, with `general`,
+//! `symmetric` and `skew-symmetric` storage expanded to the full matrix.
+//! Matrices are read densely, so sizes beyond `MAX_ELEMENTS` are refused
+//! rather than allocated.
+
+use super::{Error, Result};
+use crate::Matrix;
+use std::prelude::v1::*;
+use std::format;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Array,
+    Coordinate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Symmetry {
+    General,
+    Symmetric,
+    SkewSymmetric,
+}
+
+/// The most elements a matrix read from a file may have, 2 GiB of `f64`.
+pub const MAX_ELEMENTS: usize = 1 << 28;
+
+fn parse_error(line: usize, message: impl Into<String>) -> Error {
+    Error::Parse {
+        line,
+        message: message.into(),
+    }
+}
+
+fn parse_banner(banner: &str) -> Result<(Format, bool, Symmetry)> {
+
+    let words: Vec<String> = banner.split_whitespace().map(str::to_lowercase).collect();
+
+    if words.len() != 5 || words[0] != "%%matrixmarket" || words[1] != "matrix" {
+        return Err(parse_error(1, "expected a `%%MatrixMarket matrix ...` banner"));
+    }
+
+    let format = match words[2].as_str() {
+        "array" => Format::Array,
+        "coordinate" => Format::Coordinate,
+        other => return Err(parse_error(1, format!("unknown format {:?}", other))),
+    };
+
+    let pattern = match words[3].as_str() {
+        "real" | "integer" | "double" => false,
+        "pattern" if format == Format::Coordinate => true,
+        other => return Err(Error::Unsupported(format!("matrix market field {:?}", other))),
+    };
+
+    let symmetry = match words[4].as_str() {
+        "general" => Symmetry::General,
+        "symmetric" => Symmetry::Symmetric,
+        "skew-symmetric" => Symmetry::SkewSymmetric,
+        other => return Err(Error::Unsupported(format!("matrix market symmetry {:?}", other))),
+    };
+
+    Ok((format, pattern, symmetry))
+}
+
+fn fields(line: &str, line_no: usize, count: usize) -> Result<Vec<&str>> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() != count {
+        return Err(parse_error(line_no, format!("expected {} fields, found {}", count, fields.len())));
+    }
+    Ok(fields)
+}
+
+fn parse<T: std::str::FromStr>(field: &str, line_no: usize) -> Result<T> {
+    field
+        .parse()
+        .map_err(|_| parse_error(line_no, format!("invalid field {:?}", field)))
+}
+
+fn place(matrix: &mut Matrix<f64>, i: usize, j: usize, value: f64, symmetry: Symmetry) {
+    matrix[(i, j)] += value;
+    if i != j {
+        match symmetry {
+            Symmetry::General => {},
+            Symmetry::Symmetric => matrix[(j, i)] += value,
+            Symmetry::SkewSymmetric => matrix[(j, i)] -= value,
+        }
+    }
+}
+
+// zeroes for a `rows × cols` header, refusing sizes beyond `MAX_ELEMENTS`
+// and mirrored storage of non-square matrices
+fn zeros(rows: usize, cols: usize, symmetry: Symmetry, line_no: usize) -> Result<Matrix<f64>> {
+    if symmetry != Symmetry::General && rows != cols {
+        return Err(parse_error(line_no, format!("{:?} storage needs a square matrix, found {} × {}", symmetry, rows, cols)));
+    }
+
+    let too_large = || parse_error(line_no, format!("matrix of {} × {} is too large", rows, cols));
+    let len = rows.checked_mul(cols).filter(|&len| len <= MAX_ELEMENTS).ok_or_else(too_large)?;
+
+    let mut elements = Vec::new();
+    elements.try_reserve_exact(len).map_err(|_| too_large())?;
+    elements.resize(len, 0.0);
+
+    Ok(Matrix::new(rows, cols, elements))
+}
+
+pub fn read_matrix<R: BufRead>(reader: R) -> Result<Matrix<f64>> {
+
+    let mut lines = reader
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| line.map(|l| (idx + 1, l)));
+
+    let (_, banner) = lines.next().ok_or_else(|| parse_error(1, "empty file"))??;
+    let (format, pattern, symmetry) = parse_banner(&banner)?;
+
+    // everything after the banner, minus comments and blank lines
+    let mut data = lines.filter(|line| match line {
+        Ok((_, l)) => !l.trim().is_empty() && !l.starts_with('%'),
+        Err(_) => true,
+    });
+
+    let (size_line, size) = data.next().ok_or_else(|| parse_error(1, "missing size line"))??;
+
+    match format {
+        Format::Array => {
+            let size = fields(&size, size_line, 2)?;
+            let (rows, cols): (usize, usize) = (parse(size[0], size_line)?, parse(size[1], size_line)?);
+            let mut matrix = zeros(rows, cols, symmetry, size_line)?;
+
+            // column-major; symmetric storage only lists the lower triangle
+            let positions: Vec<(usize, usize)> = (0..cols)
+                .flat_map(|j| (0..rows).map(move |i| (i, j)))
+                .filter(|&(i, j)| match symmetry {
+                    Symmetry::General => true,
+                    Symmetry::Symmetric => i >= j,
+                    Symmetry::SkewSymmetric => i > j,
+                })
+                .collect();
+
+            for &(i, j) in &positions {
+                let (line_no, line) = data
+                    .next()
+                    .ok_or_else(|| parse_error(size_line, format!("expected {} values", positions.len())))??;
+                let value = parse(fields(&line, line_no, 1)?[0], line_no)?;
+                place(&mut matrix, i, j, value, symmetry);
+            }
+
+            Ok(matrix)
+        },
+        Format::Coordinate => {
+            let size = fields(&size, size_line, 3)?;
+            let (rows, cols, nnz): (usize, usize, usize) = (
+                parse(size[0], size_line)?,
+                parse(size[1], size_line)?,
+                parse(size[2], size_line)?,
+            );
+            let mut matrix = zeros(rows, cols, symmetry, size_line)?;
+
+            for _ in 0..nnz {
+                let (line_no, line) = data
+                    .next()
+                    .ok_or_else(|| parse_error(size_line, format!("expected {} entries", nnz)))??;
+
+                let entry = fields(&line, line_no, if pattern { 2 } else { 3 })?;
+                let (i, j): (usize, usize) = (parse(entry[0], line_no)?, parse(entry[1], line_no)?);
+                let value = if pattern { 1.0 } else { parse(entry[2], line_no)? };
+
+                if i == 0 || j == 0 || i > rows || j > cols {
+                    return Err(parse_error(line_no, format!("entry ({}, {}) is out of bounds", i, j)));
+                }
+
+                place(&mut matrix, i - 1, j - 1, value, symmetry);
+            }
+
+            Ok(matrix)
+        },
+    }
+}
+
+/// Writes `matrix` with `general` symmetry; coordinate output lists only the
+/// non-zero entries.
+pub fn write_matrix<W: Write>(mut writer: W, matrix: &Matrix<f64>, format: Format) -> Result<()> {
+
+    match format {
+        Format::Array => {
+            writeln!(writer, "%%MatrixMarket matrix array real general")?;
+            writeln!(writer, "{} {}", matrix.rows, matrix.cols)?;
+            for j in 0..matrix.cols {
+                for i in 0..matrix.rows {
+                    writeln!(writer, "{}", matrix[(i, j)])?;
+                }
+            }
+        },
+        Format::Coordinate => {
+            let nnz = matrix.elements.iter().filter(|&&x| x != 0.0).count();
+            writeln!(writer, "%%MatrixMarket matrix coordinate real general")?;
+            writeln!(writer, "{} {} {}", matrix.rows, matrix.cols, nnz)?;
+            for i in 0..matrix.rows {
+                for j in 0..matrix.cols {
+                    if matrix[(i, j)] != 0.0 {
+                        writeln!(writer, "{} {} {}", i + 1, j + 1, matrix[(i, j)])?;
+                    }
+                }
+            }
+        },
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn load_matrix(path: impl AsRef<Path>) -> Result<Matrix<f64>> {
+    read_matrix(BufReader::new(File::open(path)?))
+}
+
+pub fn save_matrix(path: impl AsRef<Path>, matrix: &Matrix<f64>, format: Format) -> Result<()> {
+    write_matrix(BufWriter::new(File::create(path)?), matrix, format)
+}
@@ -0,0 +1,467 @@
+//! NumPy `.npy` arrays and `.npz` archives.
+//!
+//! Arrays of `f32`, `f64`, `i32` and `i64` in either byte order and in C or
+//! Fortran order are read into row-major `Matrix`/`Vector` values. Matrices
+//! map to 2-d arrays, vectors to 1-d arrays.
+
+use super::zip::{self, Entry, ZipWriter};
+use super::{Error, Result};
+use crate::{Matrix, Vector};
+use std::prelude::v1::*;
+use std::format;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, Write};
+use std::path::Path;
+
+
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+const HEADER_ALIGN: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    #[default]
+    C,
+    Fortran,
+}
+
+pub trait Element: Copy + Default {
+    const KIND: char;
+    const SIZE: usize;
+
+    fn from_bytes(bytes: &[u8], big_endian: bool) -> Self;
+    fn extend_le_bytes(self, out: &mut Vec<u8>);
+
+    fn descr() -> String {
+        format!("<{}{}", Self::KIND, Self::SIZE)
+    }
+}
+
+macro_rules! impl_element {
+    ($($t:ty => $kind:expr),* $(,)?) => {
+        $(
+            impl Element for $t {
+                const KIND: char = $kind;
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                fn from_bytes(bytes: &[u8], big_endian: bool) -> Self {
+                    let bytes = bytes.try_into().unwrap();
+                    if big_endian { <$t>::from_be_bytes(bytes) } else { <$t>::from_le_bytes(bytes) }
+                }
+
+                fn extend_le_bytes(self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_element!(f32 => 'f', f64 => 'f', i32 => 'i', i64 => 'i');
+
+
+struct Header {
+    descr: String,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+// a tiny parser for the python dict literal numpy writes as the header
+struct HeaderParser<'a> {
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> HeaderParser<'a> {
+
+    fn error(&self, what: &str) -> Error {
+        Error::Format(format!("npy header: expected {} at byte {}", what, self.pos))
+    }
+
+    fn skip_ws(&mut self) {
+        while self.pos < self.src.len() && self.src[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_ws();
+        if self.src.get(self.pos) == Some(&byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("{:?}", byte as char)))
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.skip_ws();
+        let quote = match self.src.get(self.pos) {
+            Some(&q @ (b'\'' | b'"')) => q,
+            _ => return Err(self.error("string")),
+        };
+        let start = self.pos + 1;
+        let len = self.src[start..]
+            .iter()
+            .position(|&b| b == quote)
+            .ok_or_else(|| self.error("closing quote"))?;
+
+        self.pos = start + len + 1;
+        Ok(String::from_utf8_lossy(&self.src[start..start + len]).into_owned())
+    }
+
+    fn boolean(&mut self) -> Result<bool> {
+        self.skip_ws();
+        let rest = &self.src[self.pos..];
+        if rest.starts_with(b"True") {
+            self.pos += 4;
+            Ok(true)
+        } else if rest.starts_with(b"False") {
+            self.pos += 5;
+            Ok(false)
+        } else {
+            Err(self.error("True or False"))
+        }
+    }
+
+    fn integer(&mut self) -> Result<usize> {
+        self.skip_ws();
+        let start = self.pos;
+        while self.pos < self.src.len() && self.src[self.pos].is_ascii_digit() {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.src[start..self.pos])
+            .ok()
+            .and_then(|digits| digits.parse().ok())
+            .ok_or_else(|| self.error("integer"))
+    }
+
+    fn tuple(&mut self) -> Result<Vec<usize>> {
+        self.expect(b'(')?;
+        let mut items = Vec::new();
+        while !self.eat(b')') {
+            items.push(self.integer()?);
+            if !self.eat(b',') {
+                self.expect(b')')?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn header(mut self) -> Result<Header> {
+
+        let (mut descr, mut fortran_order, mut shape) = (None, None, None);
+
+        self.expect(b'{')?;
+        while !self.eat(b'}') {
+            let key = self.string()?;
+            self.expect(b':')?;
+            match key.as_str() {
+                "descr" => descr = Some(self.string()?),
+                "fortran_order" => fortran_order = Some(self.boolean()?),
+                "shape" => shape = Some(self.tuple()?),
+                _ => return Err(Error::Format(format!("npy header: unexpected key {:?}", key))),
+            }
+            if !self.eat(b',') {
+                self.expect(b'}')?;
+                break;
+            }
+        }
+
+        match (descr, fortran_order, shape) {
+            (Some(descr), Some(fortran_order), Some(shape)) => Ok(Header { descr, fortran_order, shape }),
+            _ => Err(Error::Format("npy header: missing descr, fortran_order or shape".to_string())),
+        }
+    }
+
+}
+
+fn read_header<R: Read>(reader: &mut R) -> Result<Header> {
+
+    let mut preamble = [0u8; 8];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..6] != MAGIC {
+        return Err(Error::Format("not an npy file (bad magic)".to_string()));
+    }
+
+    let header_len = match preamble[6] {
+        1 => {
+            let mut len = [0u8; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        },
+        2 | 3 => {
+            let mut len = [0u8; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        },
+        major => return Err(Error::Unsupported(format!("npy format version {}.{}", major, preamble[7]))),
+    };
+
+    // the length is untrusted, so read what's there rather than allocating it up front
+    let mut header = Vec::new();
+    reader.take(header_len as u64).read_to_end(&mut header)?;
+    if header.len() != header_len {
+        return Err(Error::Format(format!("npy header: expected {} bytes, found {}", header_len, header.len())));
+    }
+
+    HeaderParser { src: &header, pos: 0 }.header()
+}
+
+// returns the byte order of the data, or an error if it doesn't hold `T`
+fn check_descr<T: Element>(descr: &str) -> Result<bool> {
+
+    let mismatch = || Error::DtypeMismatch {
+        expected: T::descr(),
+        found: descr.to_string(),
+    };
+
+    let (big_endian, dtype) = match descr.as_bytes().first() {
+        Some(b'<') => (false, &descr[1..]),
+        Some(b'>') => (true, &descr[1..]),
+        Some(b'=') => (cfg!(target_endian = "big"), &descr[1..]),
+        _ => (cfg!(target_endian = "big"), descr),
+    };
+
+    if dtype == format!("{}{}", T::KIND, T::SIZE) {
+        Ok(big_endian)
+    } else {
+        Err(mismatch())
+    }
+}
+
+fn read_array<T: Element, R: Read>(reader: &mut R) -> Result<(Header, Vec<T>)> {
+
+    let header = read_header(reader)?;
+    let big_endian = check_descr::<T>(&header.descr)?;
+
+    let size = header.shape
+        .iter()
+        .try_fold(1usize, |acc, &dim| acc.checked_mul(dim))
+        .and_then(|len| len.checked_mul(T::SIZE))
+        .ok_or_else(|| Error::Format(format!("npy shape {:?} is too large", header.shape)))?;
+
+    // grows with the data actually read, so a corrupt shape can't force a
+    // huge allocation up front
+    let mut bytes = Vec::new();
+    reader.take(size as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != size {
+        return Err(Error::Format(format!("npy data: expected {} bytes, found {}", size, bytes.len())));
+    }
+
+    let data = bytes
+        .chunks_exact(T::SIZE)
+        .map(|chunk| T::from_bytes(chunk, big_endian))
+        .collect();
+
+    Ok((header, data))
+}
+
+fn write_header<W: Write>(writer: &mut W, descr: &str, order: Order, shape: &[usize]) -> Result<()> {
+
+    let shape = match shape {
+        [len] => format!("({},)", len),
+        dims => format!(
+            "({})",
+            dims.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", "),
+        ),
+    };
+
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+        descr,
+        if order == Order::Fortran { "True" } else { "False" },
+        shape,
+    );
+
+    // version 1.0 has a 2-byte length field; fall back to 2.0 for huge headers
+    let (version, len_bytes) = if header.len() + 1 + HEADER_ALIGN <= u16::MAX as usize { (1u8, 2) } else { (2u8, 4) };
+    let unpadded = MAGIC.len() + 2 + len_bytes + header.len() + 1;
+    header.extend(std::iter::repeat(' ').take((HEADER_ALIGN - unpadded % HEADER_ALIGN) % HEADER_ALIGN));
+    header.push('\n');
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[version, 0])?;
+    if version == 1 {
+        writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    } else {
+        writer.write_all(&(header.len() as u32).to_le_bytes())?;
+    }
+    writer.write_all(header.as_bytes())?;
+
+    Ok(())
+}
+
+fn write_data<T: Element, W: Write>(writer: &mut W, data: impl Iterator<Item = T>) -> Result<()> {
+    let mut bytes = Vec::new();
+    for x in data {
+        x.extend_le_bytes(&mut bytes);
+    }
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+pub fn read_matrix<T: Element, R: Read>(mut reader: R) -> Result<Matrix<T>> {
+
+    let (header, data) = read_array::<T, _>(&mut reader)?;
+
+    let (rows, cols) = match header.shape[..] {
+        [rows, cols] => (rows, cols),
+        _ => return Err(Error::ShapeMismatch {
+            expected: "a 2-d array".to_string(),
+            found: header.shape,
+        }),
+    };
+
+    if header.fortran_order {
+        Ok(Matrix::from_fn(rows, cols, |i, j| data[j * rows + i]))
+    } else {
+        Ok(Matrix::new(rows, cols, data))
+    }
+}
+
+pub fn read_vector<T: Element, R: Read>(mut reader: R) -> Result<Vector<T>> {
+
+    let (header, data) = read_array::<T, _>(&mut reader)?;
+
+    if header.shape.len() != 1 {
+        return Err(Error::ShapeMismatch {
+            expected: "a 1-d array".to_string(),
+            found: header.shape,
+        });
+    }
+
+    Ok(Vector::new(data))
+}
+
+pub fn write_matrix<T: Element, W: Write>(mut writer: W, matrix: &Matrix<T>, order: Order) -> Result<()> {
+
+    write_header(&mut writer, &T::descr(), order, &[matrix.rows, matrix.cols])?;
+
+    match order {
+        Order::C => write_data(&mut writer, matrix.elements.iter().copied()),
+        Order::Fortran => write_data(
+            &mut writer,
+            (0..matrix.cols).flat_map(|j| (0..matrix.rows).map(move |i| matrix[(i, j)])),
+        ),
+    }
+}
+
+pub fn write_vector<T: Element, W: Write>(mut writer: W, vector: &Vector<T>) -> Result<()> {
+    write_header(&mut writer, &T::descr(), Order::C, &[vector.elements.len()])?;
+    write_data(&mut writer, vector.elements.iter().copied())
+}
+
+pub fn load_matrix<T: Element>(path: impl AsRef<Path>) -> Result<Matrix<T>> {
+    read_matrix(BufReader::new(File::open(path)?))
+}
+
+pub fn load_vector<T: Element>(path: impl AsRef<Path>) -> Result<Vector<T>> {
+    read_vector(BufReader::new(File::open(path)?))
+}
+
+pub fn save_matrix<T: Element>(path: impl AsRef<Path>, matrix: &Matrix<T>) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_matrix(&mut writer, matrix, Order::C)?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn save_vector<T: Element>(path: impl AsRef<Path>, vector: &Vector<T>) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_vector(&mut writer, vector)?;
+    writer.flush()?;
+    Ok(())
+}
+
+
+/// Reads named arrays out of an `.npz` archive written by `np.savez`.
+pub struct NpzReader<R> {
+    reader: R,
+    entries: Vec<Entry>,
+}
+
+impl NpzReader<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        NpzReader::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> NpzReader<R> {
+
+    pub fn new(mut reader: R) -> Result<Self> {
+        let entries = zip::read_entries(&mut reader)?;
+        Ok(NpzReader { reader, entries })
+    }
+
+    /// Array names, without the `.npy` suffix numpy stores them under.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries
+            .iter()
+            .map(|entry| entry.name.strip_suffix(".npy").unwrap_or(&entry.name))
+    }
+
+    fn entry_bytes(&mut self, name: &str) -> Result<Vec<u8>> {
+        let entry = self.entries
+            .iter()
+            .find(|entry| entry.name.strip_suffix(".npy") == Some(name) || entry.name == name)
+            .ok_or_else(|| Error::MissingArray(name.to_string()))?;
+
+        zip::read_entry(&mut self.reader, entry)
+    }
+
+    pub fn matrix<T: Element>(&mut self, name: &str) -> Result<Matrix<T>> {
+        read_matrix(self.entry_bytes(name)?.as_slice())
+    }
+
+    pub fn vector<T: Element>(&mut self, name: &str) -> Result<Vector<T>> {
+        read_vector(self.entry_bytes(name)?.as_slice())
+    }
+
+}
+
+/// Writes named arrays into an `.npz` archive readable by `np.load`.
+pub struct NpzWriter<W: Write> {
+    zip: ZipWriter<W>,
+}
+
+impl NpzWriter<BufWriter<File>> {
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(NpzWriter::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> NpzWriter<W> {
+
+    pub fn new(writer: W) -> Self {
+        NpzWriter {
+            zip: ZipWriter::new(writer),
+        }
+    }
+
+    pub fn add_matrix<T: Element>(&mut self, name: &str, matrix: &Matrix<T>) -> Result<()> {
+        let mut bytes = Vec::new();
+        write_matrix(&mut bytes, matrix, Order::C)?;
+        self.zip.add(&format!("{}.npy", name), &bytes)
+    }
+
+    pub fn add_vector<T: Element>(&mut self, name: &str, vector: &Vector<T>) -> Result<()> {
+        let mut bytes = Vec::new();
+        write_vector(&mut bytes, vector)?;
+        self.zip.add(&format!("{}.npy", name), &bytes)
+    }
+
+    /// Writes the archive's central directory and hands back the writer.
+    pub fn finish(self) -> Result<W> {
+        self.zip.finish()
+    }
+
+}
@@ -0,0 +1,316 @@
+//! Just enough of the zip container to read and write `.npz` archives.
+//!
+//! Only stored (uncompressed) entries are handled, which is what
+//! `np.savez` produces. Zip64 extensions are understood when reading.
+
+use super::{Error, Result};
+use std::prelude::v1::*;
+use std::{format, vec};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+
+const LOCAL_HEADER_SIG: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIG: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIR_SIG: u32 = 0x0605_4b50;
+const ZIP64_END_OF_CENTRAL_DIR_SIG: u32 = 0x0606_4b50;
+const ZIP64_LOCATOR_SIG: u32 = 0x0706_4b50;
+const ZIP64_EXTRA_ID: u16 = 0x0001;
+
+const METHOD_STORED: u16 = 0;
+// 1980-01-01, the earliest date a zip entry can carry
+const DOS_DATE: u16 = 0x0021;
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+}
+
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Entry {
+    pub name: String,
+    method: u16,
+    crc: u32,
+    size: u64,
+    compressed_size: u64,
+    offset: u64,
+}
+
+fn u16_at(buf: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([buf[at], buf[at + 1]])
+}
+
+fn u32_at(buf: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(buf[at..at + 4].try_into().unwrap())
+}
+
+fn u64_at(buf: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(buf[at..at + 8].try_into().unwrap())
+}
+
+fn truncated() -> Error {
+    Error::Format("truncated zip archive".to_string())
+}
+
+fn read_exact_at<R: Read + Seek>(reader: &mut R, offset: u64, len: usize) -> Result<Vec<u8>> {
+    let mut buf = vec![0u8; len];
+    reader.seek(SeekFrom::Start(offset))?;
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+pub(crate) fn read_entries<R: Read + Seek>(reader: &mut R) -> Result<Vec<Entry>> {
+
+    let file_len = reader.seek(SeekFrom::End(0))?;
+
+    // end of central directory is 22 bytes plus an optional comment of up to 64k
+    let tail_len = file_len.min(22 + u16::MAX as u64);
+    let tail = read_exact_at(reader, file_len - tail_len, tail_len as usize)?;
+
+    let eocd = (0..tail.len().saturating_sub(21))
+        .rev()
+        .find(|&i| u32_at(&tail, i) == END_OF_CENTRAL_DIR_SIG)
+        .ok_or_else(|| Error::Format("missing zip end of central directory".to_string()))?;
+
+    let mut count = u16_at(&tail, eocd + 10) as u64;
+    let mut cd_size = u32_at(&tail, eocd + 12) as u64;
+    let mut cd_offset = u32_at(&tail, eocd + 16) as u64;
+
+    if count == u16::MAX as u64 || cd_size == u32::MAX as u64 || cd_offset == u32::MAX as u64 {
+        let locator_at = (file_len - tail_len + eocd as u64)
+            .checked_sub(20)
+            .ok_or_else(truncated)?;
+        let locator = read_exact_at(reader, locator_at, 20)?;
+        if u32_at(&locator, 0) != ZIP64_LOCATOR_SIG {
+            return Err(Error::Format("missing zip64 end of central directory locator".to_string()));
+        }
+
+        let record = read_exact_at(reader, u64_at(&locator, 8), 56)?;
+        if u32_at(&record, 0) != ZIP64_END_OF_CENTRAL_DIR_SIG {
+            return Err(Error::Format("bad zip64 end of central directory".to_string()));
+        }
+
+        count = u64_at(&record, 32);
+        cd_size = u64_at(&record, 40);
+        cd_offset = u64_at(&record, 48);
+    }
+
+    if cd_offset.checked_add(cd_size).map_or(true, |end| end > file_len) {
+        return Err(truncated());
+    }
+
+    let cd = read_exact_at(reader, cd_offset, cd_size as usize)?;
+    // every entry takes at least 46 bytes, which bounds an untrusted count
+    let mut entries = Vec::with_capacity(count.min(cd.len() as u64 / 46) as usize);
+    let mut at = 0;
+
+    for _ in 0..count {
+        if at + 46 > cd.len() || u32_at(&cd, at) != CENTRAL_HEADER_SIG {
+            return Err(Error::Format("bad zip central directory entry".to_string()));
+        }
+
+        let name_len = u16_at(&cd, at + 28) as usize;
+        let extra_len = u16_at(&cd, at + 30) as usize;
+        let comment_len = u16_at(&cd, at + 32) as usize;
+        let name_end = at + 46 + name_len;
+        let extra_end = name_end + extra_len;
+        if extra_end + comment_len > cd.len() {
+            return Err(truncated());
+        }
+
+        let mut entry = Entry {
+            name: String::from_utf8_lossy(&cd[at + 46..name_end]).into_owned(),
+            method: u16_at(&cd, at + 10),
+            crc: u32_at(&cd, at + 16),
+            compressed_size: u32_at(&cd, at + 20) as u64,
+            size: u32_at(&cd, at + 24) as u64,
+            offset: u32_at(&cd, at + 42) as u64,
+        };
+
+        apply_zip64_extra(&mut entry, &cd[name_end..extra_end])?;
+
+        entries.push(entry);
+        at = extra_end + comment_len;
+    }
+
+    Ok(entries)
+}
+
+// the zip64 extra field only carries the values whose 32-bit slots are saturated, in this order
+fn apply_zip64_extra(entry: &mut Entry, mut extra: &[u8]) -> Result<()> {
+
+    while extra.len() >= 4 {
+        let id = u16_at(extra, 0);
+        let len = u16_at(extra, 2) as usize;
+        if 4 + len > extra.len() {
+            return Err(truncated());
+        }
+
+        if id == ZIP64_EXTRA_ID {
+            let mut values = extra[4..4 + len].chunks_exact(8).map(|c| u64_at(c, 0));
+            for field in [&mut entry.size, &mut entry.compressed_size, &mut entry.offset] {
+                if *field == u32::MAX as u64 {
+                    *field = values.next().ok_or_else(truncated)?;
+                }
+            }
+        }
+
+        extra = &extra[4 + len..];
+    }
+
+    Ok(())
+}
+
+pub(crate) fn read_entry<R: Read + Seek>(reader: &mut R, entry: &Entry) -> Result<Vec<u8>> {
+
+    if entry.method != METHOD_STORED {
+        return Err(Error::Unsupported(format!(
+            "zip entry {:?} uses compression method {} (only stored entries, as written by np.savez, are supported)",
+            entry.name, entry.method,
+        )));
+    }
+
+    let header = read_exact_at(reader, entry.offset, 30)?;
+    if u32_at(&header, 0) != LOCAL_HEADER_SIG {
+        return Err(Error::Format(format!("bad zip local header for {:?}", entry.name)));
+    }
+
+    // the sizes are untrusted, so check them against the archive before allocating
+    let file_len = reader.seek(SeekFrom::End(0))?;
+    let data_at = entry.offset + 30 + u16_at(&header, 26) as u64 + u16_at(&header, 28) as u64;
+    if data_at.checked_add(entry.compressed_size).map_or(true, |end| end > file_len) {
+        return Err(truncated());
+    }
+
+    let data = read_exact_at(reader, data_at, entry.compressed_size as usize)?;
+
+    if data.len() as u64 != entry.size || crc32(&data) != entry.crc {
+        return Err(Error::Format(format!("checksum mismatch in zip entry {:?}", entry.name)));
+    }
+
+    Ok(data)
+}
+
+pub(crate) struct ZipWriter<W> {
+    inner: W,
+    offset: u64,
+    entries: Vec<Entry>,
+}
+
+impl<W: Write> ZipWriter<W> {
+
+    pub fn new(inner: W) -> Self {
+        ZipWriter {
+            inner,
+            offset: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, name: &str, data: &[u8]) -> Result<()> {
+
+        if data.len() as u64 >= u32::MAX as u64 || self.offset >= u32::MAX as u64 {
+            return Err(Error::Unsupported("npz archives larger than 4 GiB".to_string()));
+        }
+        if name.len() > u16::MAX as usize {
+            return Err(Error::Unsupported("npz array names longer than 65535 bytes".to_string()));
+        }
+        if self.entries.len() >= u16::MAX as usize {
+            return Err(Error::Unsupported("npz archives with more than 65534 arrays".to_string()));
+        }
+
+        let entry = Entry {
+            name: name.to_string(),
+            method: METHOD_STORED,
+            crc: crc32(data),
+            size: data.len() as u64,
+            compressed_size: data.len() as u64,
+            offset: self.offset,
+        };
+
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend_from_slice(&LOCAL_HEADER_SIG.to_le_bytes());
+        header.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        header.extend_from_slice(&0u16.to_le_bytes()); // flags
+        header.extend_from_slice(&METHOD_STORED.to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes()); // time
+        header.extend_from_slice(&DOS_DATE.to_le_bytes());
+        header.extend_from_slice(&entry.crc.to_le_bytes());
+        header.extend_from_slice(&(entry.compressed_size as u32).to_le_bytes());
+        header.extend_from_slice(&(entry.size as u32).to_le_bytes());
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes()); // extra length
+        header.extend_from_slice(name.as_bytes());
+
+        self.inner.write_all(&header)?;
+        self.inner.write_all(data)?;
+
+        self.offset += (header.len() + data.len()) as u64;
+        self.entries.push(entry);
+
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<W> {
+
+        let mut cd = Vec::new();
+
+        for entry in &self.entries {
+            cd.extend_from_slice(&CENTRAL_HEADER_SIG.to_le_bytes());
+            cd.extend_from_slice(&20u16.to_le_bytes()); // version made by
+            cd.extend_from_slice(&20u16.to_le_bytes()); // version needed
+            cd.extend_from_slice(&0u16.to_le_bytes()); // flags
+            cd.extend_from_slice(&entry.method.to_le_bytes());
+            cd.extend_from_slice(&0u16.to_le_bytes()); // time
+            cd.extend_from_slice(&DOS_DATE.to_le_bytes());
+            cd.extend_from_slice(&entry.crc.to_le_bytes());
+            cd.extend_from_slice(&(entry.compressed_size as u32).to_le_bytes());
+            cd.extend_from_slice(&(entry.size as u32).to_le_bytes());
+            cd.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+            cd.extend_from_slice(&[0u8; 12]); // extra, comment, disk, internal and external attributes
+            cd.extend_from_slice(&(entry.offset as u32).to_le_bytes());
+            cd.extend_from_slice(entry.name.as_bytes());
+        }
+
+        if self.offset + cd.len() as u64 >= u32::MAX as u64 {
+            return Err(Error::Unsupported("npz archives larger than 4 GiB".to_string()));
+        }
+
+        let count = self.entries.len() as u16;
+        let mut eocd = Vec::with_capacity(22);
+        eocd.extend_from_slice(&END_OF_CENTRAL_DIR_SIG.to_le_bytes());
+        eocd.extend_from_slice(&[0u8; 4]); // disk numbers
+        eocd.extend_from_slice(&count.to_le_bytes());
+        eocd.extend_from_slice(&count.to_le_bytes());
+        eocd.extend_from_slice(&(cd.len() as u32).to_le_bytes());
+        eocd.extend_from_slice(&(self.offset as u32).to_le_bytes());
+        eocd.extend_from_slice(&0u16.to_le_bytes()); // comment length
+
+        self.inner.write_all(&cd)?;
+        self.inner.write_all(&eocd)?;
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+
+}
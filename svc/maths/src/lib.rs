@@ -1,6 +1,46 @@
+#![no_std]
+
+//! Without the default `std` feature the crate only needs `alloc`; enable
+//! `libm` in that case for the float functions `std` would otherwise provide.
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("maths needs either the `std` or the `libm` feature for float functions");
+
+#[doc(hidden)]
+pub use alloc::vec as __vec;
+
+#[macro_use]
+mod macros;
+
 pub mod vector;
 pub mod matrix;
+pub mod storage;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod approx;
+pub mod simd;
+pub mod float;
+pub mod linalg;
+pub mod diff;
+pub mod optimize;
+pub mod roots;
+pub mod quadrature;
+pub mod ode;
+pub mod interp;
+pub mod polynomial;
+
+mod format;
 
 pub use vector::Vector;
 pub use matrix::Matrix;
-
+pub use storage::{Storage, StorageMut};
+pub use format::ParseError;
+pub use approx::ApproxEq;
+pub use float::Float;
+pub use linalg::{Field, Lu, Qr};
+pub use polynomial::Polynomial;
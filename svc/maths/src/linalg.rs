@@ -0,0 +1,727 @@
+//! Gaussian elimination over any `Field`: LU factorisation, solving,
+//! determinant, rank, inverse and reduced row echelon form. Householder QR
+//! and least squares are for `f64` only.
+//!
+//! Floats pick the largest pivot in each column and treat entries within
+//! rounding error as zero. Exact types such as `num::BigRational` only ever
+//! treat an exact zero as zero, so the results carry no rounding error.
+
+use crate::{Matrix, Vector};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use alloc::{vec, vec::Vec};
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+
+pub trait Field:
+    Clone
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+
+    /// Size used to rank pivot candidates; only compared, never computed with.
+    fn magnitude(&self) -> f64;
+
+    /// Relative rounding error of one operation, zero for exact types.
+    fn epsilon() -> f64;
+}
+
+macro_rules! impl_float_field {
+    ($($t:ty),*) => {
+        $(
+            impl Field for $t {
+                fn zero() -> Self {
+                    0.0
+                }
+
+                fn one() -> Self {
+                    1.0
+                }
+
+                fn magnitude(&self) -> f64 {
+                    self.abs() as f64
+                }
+
+                fn epsilon() -> f64 {
+                    <$t>::EPSILON as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_float_field!(f32, f64);
+
+#[cfg(feature = "num")]
+impl<T> Field for num::rational::Ratio<T>
+where
+    T: Clone + num::Integer + num::Signed + num::ToPrimitive + num::bigint::ToBigInt,
+{
+    fn zero() -> Self {
+        num::Zero::zero()
+    }
+
+    fn one() -> Self {
+        num::One::one()
+    }
+
+    fn magnitude(&self) -> f64 {
+        num::ToPrimitive::to_f64(self).map_or(f64::INFINITY, |x| x.abs())
+    }
+
+    fn epsilon() -> f64 {
+        0.0
+    }
+}
+
+
+// entries no larger than this are rounding noise, following numpy's `matrix_rank`
+fn tolerance<T: Field>(matrix: &Matrix<T>) -> f64 {
+    if T::epsilon() == 0.0 {
+        return 0.0;
+    }
+
+    let scale = matrix.elements.iter().map(T::magnitude).fold(0.0, f64::max);
+    scale * T::epsilon() * matrix.rows.max(matrix.cols) as f64
+}
+
+fn is_zero<T: Field>(x: &T, tolerance: f64) -> bool {
+    *x == T::zero() || (tolerance > 0.0 && x.magnitude() <= tolerance)
+}
+
+// index of the best pivot in `col` from `from` down, if any is non-zero
+fn find_pivot<T: Field>(matrix: &Matrix<T>, col: usize, from: usize, tolerance: f64) -> Option<usize> {
+    let mut candidates = (from..matrix.rows).filter(|&row| !is_zero(&matrix[(row, col)], tolerance));
+
+    if T::epsilon() == 0.0 {
+        // any non-zero pivot is exact; the first keeps row order stable
+        return candidates.next();
+    }
+
+    candidates.fold(None, |best: Option<usize>, row| match best {
+        Some(b) if matrix[(b, col)].magnitude() >= matrix[(row, col)].magnitude() => Some(b),
+        _ => Some(row),
+    })
+}
+
+fn swap_rows<T>(matrix: &mut Matrix<T>, a: usize, b: usize) {
+    if a != b {
+        for col in 0..matrix.cols {
+            matrix.elements.swap(a * matrix.cols + col, b * matrix.cols + col);
+        }
+    }
+}
+
+
+/// LU factorisation with row pivoting, `P * A = L * U`.
+#[derive(Debug, Clone)]
+pub struct Lu<T> {
+    // L below the diagonal (its unit diagonal implied) and U on and above it
+    factors: Matrix<T>,
+    permutation: Vec<usize>,
+    swaps: usize,
+    singular: bool,
+}
+
+impl<T: Field> Lu<T> {
+
+    pub fn new(matrix: &Matrix<T>) -> Self {
+        assert_eq!(matrix.rows, matrix.cols, "LU factorisation needs a square matrix.");
+
+        let n = matrix.rows;
+        let tolerance = tolerance(matrix);
+        let mut factors = matrix.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut swaps = 0;
+        let mut singular = false;
+
+        for col in 0..n {
+            let Some(pivot) = find_pivot(&factors, col, col, tolerance) else {
+                singular = true;
+                continue;
+            };
+
+            if pivot != col {
+                swap_rows(&mut factors, pivot, col);
+                permutation.swap(pivot, col);
+                swaps += 1;
+            }
+
+            for row in col + 1..n {
+                let factor = factors[(row, col)].clone() / factors[(col, col)].clone();
+                for k in col + 1..n {
+                    factors[(row, k)] = factors[(row, k)].clone() - factor.clone() * factors[(col, k)].clone();
+                }
+                factors[(row, col)] = factor;
+            }
+        }
+
+        Lu {
+            factors,
+            permutation,
+            swaps,
+            singular,
+        }
+    }
+
+    pub fn is_singular(&self) -> bool {
+        self.singular
+    }
+
+    /// Row `i` of `P * A` is row `permutation()[i]` of `A`.
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    pub fn l(&self) -> Matrix<T> {
+        Matrix::from_fn(self.factors.rows, self.factors.cols, |i, j| match i.cmp(&j) {
+            core::cmp::Ordering::Greater => self.factors[(i, j)].clone(),
+            core::cmp::Ordering::Equal => T::one(),
+            core::cmp::Ordering::Less => T::zero(),
+        })
+    }
+
+    pub fn u(&self) -> Matrix<T> {
+        Matrix::from_fn(self.factors.rows, self.factors.cols, |i, j| {
+            if i <= j { self.factors[(i, j)].clone() } else { T::zero() }
+        })
+    }
+
+    pub fn determinant(&self) -> T {
+        if self.singular {
+            return T::zero();
+        }
+
+        let det = (0..self.factors.rows).fold(T::one(), |det, i| det * self.factors[(i, i)].clone());
+        if self.swaps % 2 == 1 { -det } else { det }
+    }
+
+    /// Solves `A x = b`, or `None` if `A` is singular.
+    pub fn solve(&self, b: &Vector<T>) -> Option<Vector<T>> {
+        assert_eq!(self.factors.rows, b.len(), "Right-hand side must match the matrix size.");
+
+        if self.singular {
+            return None;
+        }
+
+        let n = self.factors.rows;
+        let mut x: Vec<T> = self.permutation.iter().map(|&i| b[i].clone()).collect();
+
+        for i in 0..n {
+            for k in 0..i {
+                x[i] = x[i].clone() - self.factors[(i, k)].clone() * x[k].clone();
+            }
+        }
+
+        for i in (0..n).rev() {
+            for k in i + 1..n {
+                x[i] = x[i].clone() - self.factors[(i, k)].clone() * x[k].clone();
+            }
+            x[i] = x[i].clone() / self.factors[(i, i)].clone();
+        }
+
+        Some(Vector::new(x))
+    }
+
+    pub fn inverse(&self) -> Option<Matrix<T>> {
+        if self.singular {
+            return None;
+        }
+
+        let n = self.factors.rows;
+        let mut elements = vec![T::zero(); n * n];
+
+        for col in 0..n {
+            let unit = (0..n).map(|i| if i == col { T::one() } else { T::zero() }).collect();
+            let x = self.solve(&unit)?;
+            for (row, value) in x.into_iter().enumerate() {
+                elements[row * n + col] = value;
+            }
+        }
+
+        Some(Matrix::new(n, n, elements))
+    }
+
+}
+
+
+impl<T: Field> Matrix<T> {
+
+    pub fn identity(n: usize) -> Self {
+        Matrix::from_fn(n, n, |i, j| if i == j { T::one() } else { T::zero() })
+    }
+
+    pub fn lu(&self) -> Lu<T> {
+        Lu::new(self)
+    }
+
+    pub fn solve(&self, b: &Vector<T>) -> Option<Vector<T>> {
+        self.lu().solve(b)
+    }
+
+    pub fn determinant(&self) -> T {
+        self.lu().determinant()
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        self.lu().inverse()
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    /// Reduced row echelon form, along with the pivot column of each
+    /// non-zero row.
+    pub fn rref(&self) -> (Self, Vec<usize>) {
+        let tolerance = tolerance(self);
+        let mut m = self.clone();
+        let mut pivots = Vec::new();
+
+        for col in 0..m.cols {
+            let row = pivots.len();
+            if row == m.rows {
+                break;
+            }
+
+            let Some(pivot) = find_pivot(&m, col, row, tolerance) else {
+                // clear the rounding noise left in a column without a pivot
+                for r in row..m.rows {
+                    m[(r, col)] = T::zero();
+                }
+                continue;
+            };
+            swap_rows(&mut m, pivot, row);
+
+            let scale = m[(row, col)].clone();
+            for k in col..m.cols {
+                m[(row, k)] = m[(row, k)].clone() / scale.clone();
+            }
+
+            for r in (0..m.rows).filter(|&r| r != row) {
+                let factor = m[(r, col)].clone();
+                if factor == T::zero() {
+                    continue;
+                }
+                for k in col..m.cols {
+                    m[(r, k)] = m[(r, k)].clone() - factor.clone() * m[(row, k)].clone();
+                }
+            }
+
+            pivots.push(col);
+        }
+
+        (m, pivots)
+    }
+
+}
+
+
+/// Householder QR factorisation, `A = Q * R`, for least-squares problems.
+#[derive(Debug, Clone)]
+pub struct Qr {
+    // R in and above the diagonal; the reflectors are kept separately
+    r: Matrix<f64>,
+    // unit Householder vectors, reflector k acting on rows k..
+    reflectors: Vec<Vector<f64>>,
+}
+
+impl Qr {
+
+    pub fn new(matrix: &Matrix<f64>) -> Self {
+        let (m, n) = (matrix.rows, matrix.cols);
+        let mut r = matrix.clone();
+        let mut reflectors = Vec::with_capacity(n.min(m));
+
+        for k in 0..n.min(m) {
+            let mut v: Vector<f64> = (k..m).map(|i| r[(i, k)]).collect();
+            let norm = v.dot(&v).sqrt();
+            // reflecting onto the side away from x keeps v well away from zero
+            let alpha = if v[0] > 0.0 { -norm } else { norm };
+            v[0] -= alpha;
+
+            let length = v.dot(&v).sqrt();
+            if length > 0.0 {
+                v = &v * (1.0 / length);
+                for j in k..n {
+                    let dot: f64 = (k..m).map(|i| v[i - k] * r[(i, j)]).sum();
+                    for i in k..m {
+                        r[(i, j)] -= 2.0 * v[i - k] * dot;
+                    }
+                }
+            }
+
+            reflectors.push(v);
+        }
+
+        Qr { r, reflectors }
+    }
+
+    fn reflect(&self, k: usize, x: &mut [f64]) {
+        let v = &self.reflectors[k];
+        let dot: f64 = v.iter().zip(&x[k..]).map(|(a, b)| a * b).sum();
+        for (xi, vi) in x[k..].iter_mut().zip(v.iter()) {
+            *xi -= 2.0 * vi * dot;
+        }
+    }
+
+    /// The full `m × m` orthogonal factor.
+    pub fn q(&self) -> Matrix<f64> {
+        let m = self.r.rows;
+        let mut q = Matrix::identity(m);
+
+        for col in 0..m {
+            let mut e: Vec<f64> = (0..m).map(|row| q[(row, col)]).collect();
+            for k in (0..self.reflectors.len()).rev() {
+                self.reflect(k, &mut e);
+            }
+            for (row, value) in e.into_iter().enumerate() {
+                q[(row, col)] = value;
+            }
+        }
+
+        q
+    }
+
+    pub fn r(&self) -> Matrix<f64> {
+        Matrix::from_fn(self.r.rows, self.r.cols, |i, j| if i <= j { self.r[(i, j)] } else { 0.0 })
+    }
+
+    /// Minimises `|A x - b|`, or `None` if `A` has fewer rows than columns
+    /// or is rank deficient.
+    pub fn solve_least_squares(&self, b: &Vector<f64>) -> Option<Vector<f64>> {
+        let (m, n) = (self.r.rows, self.r.cols);
+        assert_eq!(m, b.len(), "Right-hand side must have one entry per matrix row.");
+
+        if m < n {
+            return None;
+        }
+
+        // rounding leaves the diagonal of a rank-deficient R at a few ulps of its largest entry
+        let scale = (0..n)
+            .flat_map(|j| (0..=j).map(move |i| (i, j)))
+            .map(|idx| self.r[idx].abs())
+            .fold(0.0, f64::max);
+        let tolerance = scale * f64::EPSILON * m as f64;
+        if (0..n).any(|i| self.r[(i, i)].abs() <= tolerance) {
+            return None;
+        }
+
+        let mut qtb = b.elements.clone();
+        for k in 0..self.reflectors.len() {
+            self.reflect(k, &mut qtb);
+        }
+
+        let mut x = vec![0.0; n];
+        for i in (0..n).rev() {
+            let tail: f64 = (i + 1..n).map(|k| self.r[(i, k)] * x[k]).sum();
+            x[i] = (qtb[i] - tail) / self.r[(i, i)];
+        }
+
+        Some(Vector::new(x))
+    }
+
+}
+
+impl Matrix<f64> {
+
+    pub fn qr(&self) -> Qr {
+        Qr::new(self)
+    }
+
+    /// Least-squares solution of `A x ≈ b` via QR.
+    pub fn least_squares(&self, b: &Vector<f64>) -> Option<Vector<f64>> {
+        self.qr().solve_least_squares(b)
+    }
+
+}
+
+
+// |magnitude| with the sign of `sign`
+#[cfg(feature = "num")]
+fn with_sign(magnitude: f64, sign: f64) -> f64 {
+    if sign >= 0.0 { magnitude.abs() } else { -magnitude.abs() }
+}
+
+#[cfg(feature = "num")]
+impl Matrix<f64> {
+
+    /// Eigenvalues of a square matrix, in no particular order, by balancing,
+    /// reduction to Hessenberg form and shifted QR iteration. `None` if the
+    /// iteration fails to converge.
+    pub fn eigenvalues(&self) -> Option<Vec<num::complex::Complex64>> {
+        assert_eq!(self.rows, self.cols, "Eigenvalues need a square matrix.");
+
+        let mut h = self.clone();
+        balance(&mut h);
+        hessenberg(&mut h);
+        hessenberg_eigenvalues(h)
+    }
+
+}
+
+// rescales rows and columns by powers of two so their norms are comparable,
+// which leaves the eigenvalues unchanged but improves their accuracy
+#[cfg(feature = "num")]
+fn balance(a: &mut Matrix<f64>) {
+    const RADIX: f64 = 2.0;
+    let n = a.rows;
+    let mut done = false;
+
+    while !done {
+        done = true;
+        for i in 0..n {
+            let (mut c, mut r) = (0.0, 0.0);
+            for j in (0..n).filter(|&j| j != i) {
+                c += a[(j, i)].abs();
+                r += a[(i, j)].abs();
+            }
+            if c == 0.0 || r == 0.0 {
+                continue;
+            }
+
+            let s = c + r;
+            let mut f = 1.0;
+            while c < r / RADIX {
+                f *= RADIX;
+                c *= RADIX * RADIX;
+            }
+            while c > r * RADIX {
+                f /= RADIX;
+                c /= RADIX * RADIX;
+            }
+
+            if (c + r) / f < 0.95 * s {
+                done = false;
+                for j in 0..n {
+                    a[(i, j)] /= f;
+                    a[(j, i)] *= f;
+                }
+            }
+        }
+    }
+}
+
+// reduction to upper Hessenberg form by stabilised elimination
+#[cfg(feature = "num")]
+fn hessenberg(a: &mut Matrix<f64>) {
+    let n = a.rows;
+
+    for m in 1..n.saturating_sub(1) {
+        let pivot = (m..n).fold(m, |best, j| if a[(j, m - 1)].abs() > a[(best, m - 1)].abs() { j } else { best });
+        let x = a[(pivot, m - 1)];
+
+        if pivot != m {
+            for j in m - 1..n {
+                a.elements.swap(pivot * n + j, m * n + j);
+            }
+            for j in 0..n {
+                a.elements.swap(j * n + pivot, j * n + m);
+            }
+        }
+
+        if x == 0.0 {
+            continue;
+        }
+
+        for i in m + 1..n {
+            let y = a[(i, m - 1)] / x;
+            if y == 0.0 {
+                continue;
+            }
+            a[(i, m - 1)] = 0.0;
+            for j in m..n {
+                a[(i, j)] -= y * a[(m, j)];
+            }
+            for j in 0..n {
+                a[(j, m)] += y * a[(j, i)];
+            }
+        }
+    }
+}
+
+// Francis double-shift QR on an upper Hessenberg matrix
+#[cfg(feature = "num")]
+fn hessenberg_eigenvalues(mut a: Matrix<f64>) -> Option<Vec<num::complex::Complex64>> {
+    use num::complex::Complex64;
+
+    let n = a.rows as isize;
+    let mut eigenvalues = vec![Complex64::new(0.0, 0.0); a.rows];
+
+    macro_rules! a {
+        ($i:expr, $j:expr) => {
+            a[(($i) as usize, ($j) as usize)]
+        };
+    }
+
+    let norm: f64 = (0..n)
+        .flat_map(|i| ((i - 1).max(0)..n).map(move |j| (i, j)))
+        .map(|(i, j)| a[(i as usize, j as usize)].abs())
+        .sum();
+
+    let mut nn = n - 1;
+    let mut shift = 0.0;
+
+    while nn >= 0 {
+        let mut iterations = 0;
+
+        loop {
+            // look for a negligible subdiagonal element to split at
+            let mut l = nn;
+            while l > 0 {
+                let mut s = a!(l - 1, l - 1).abs() + a!(l, l).abs();
+                if s == 0.0 {
+                    s = norm;
+                }
+                if a!(l, l - 1).abs() <= f64::EPSILON * s {
+                    a!(l, l - 1) = 0.0;
+                    break;
+                }
+                l -= 1;
+            }
+
+            let mut x = a!(nn, nn);
+
+            if l == nn {
+                // one root found
+                eigenvalues[nn as usize] = Complex64::new(x + shift, 0.0);
+                nn -= 1;
+                break;
+            }
+
+            let mut y = a!(nn - 1, nn - 1);
+            let mut w = a!(nn, nn - 1) * a!(nn - 1, nn);
+
+            if l == nn - 1 {
+                // two roots found, from the trailing 2x2 block
+                let p = 0.5 * (y - x);
+                let q = p * p + w;
+                let z = q.abs().sqrt();
+                x += shift;
+
+                if q >= 0.0 {
+                    let z = p + with_sign(z, p);
+                    let second = if z != 0.0 { x - w / z } else { x + z };
+                    eigenvalues[(nn - 1) as usize] = Complex64::new(x + z, 0.0);
+                    eigenvalues[nn as usize] = Complex64::new(second, 0.0);
+                } else {
+                    eigenvalues[(nn - 1) as usize] = Complex64::new(x + p, z);
+                    eigenvalues[nn as usize] = Complex64::new(x + p, -z);
+                }
+                nn -= 2;
+                break;
+            }
+
+            if iterations == 30 {
+                return None;
+            }
+            if iterations == 10 || iterations == 20 {
+                // exceptional shift to break cycles
+                shift += x;
+                for i in 0..=nn {
+                    a!(i, i) -= x;
+                }
+                let s = a!(nn, nn - 1).abs() + a!(nn - 1, nn - 2).abs();
+                x = 0.75 * s;
+                y = x;
+                w = -0.4375 * s * s;
+            }
+            iterations += 1;
+
+            // look for two consecutive small subdiagonal elements
+            let (mut p, mut q, mut r);
+            let mut m = nn - 2;
+            loop {
+                let z = a!(m, m);
+                let rr = x - z;
+                let s = y - z;
+                p = (rr * s - w) / a!(m + 1, m) + a!(m, m + 1);
+                q = a!(m + 1, m + 1) - z - rr - s;
+                r = a!(m + 2, m + 1);
+                let s = p.abs() + q.abs() + r.abs();
+                p /= s;
+                q /= s;
+                r /= s;
+                if m == l {
+                    break;
+                }
+                let u = a!(m, m - 1).abs() * (q.abs() + r.abs());
+                let v = p.abs() * (a!(m - 1, m - 1).abs() + z.abs() + a!(m + 1, m + 1).abs());
+                if u <= f64::EPSILON * v {
+                    break;
+                }
+                m -= 1;
+            }
+
+            for i in m..nn - 1 {
+                a!(i + 2, i) = 0.0;
+                if i != m {
+                    a!(i + 2, i - 1) = 0.0;
+                }
+            }
+
+            // double QR step on rows l..=nn and columns m..=nn
+            for k in m..nn {
+                if k != m {
+                    p = a!(k, k - 1);
+                    q = a!(k + 1, k - 1);
+                    r = if k + 1 != nn { a!(k + 2, k - 1) } else { 0.0 };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x != 0.0 {
+                        p /= x;
+                        q /= x;
+                        r /= x;
+                    }
+                }
+
+                let s = with_sign((p * p + q * q + r * r).sqrt(), p);
+                if s == 0.0 {
+                    continue;
+                }
+
+                if k == m {
+                    if l != m {
+                        a!(k, k - 1) = -a!(k, k - 1);
+                    }
+                } else {
+                    a!(k, k - 1) = -s * x;
+                }
+
+                p += s;
+                x = p / s;
+                y = q / s;
+                let z = r / s;
+                q /= p;
+                r /= p;
+
+                for j in k..=nn {
+                    let mut p = a!(k, j) + q * a!(k + 1, j);
+                    if k + 1 != nn {
+                        p += r * a!(k + 2, j);
+                        a!(k + 2, j) -= p * z;
+                    }
+                    a!(k + 1, j) -= p * y;
+                    a!(k, j) -= p * x;
+                }
+
+                for i in l..=nn.min(k + 3) {
+                    let mut p = x * a!(i, k) + y * a!(i, k + 1);
+                    if k + 1 != nn {
+                        p += z * a!(i, k + 2);
+                        a!(i, k + 2) -= p * r;
+                    }
+                    a!(i, k + 1) -= p * q;
+                    a!(i, k) -= p;
+                }
+            }
+        }
+    }
+
+    Some(eigenvalues)
+}
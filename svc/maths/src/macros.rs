@@ -0,0 +1,61 @@
+/// Builds a `Vector` like `vec!`: `vector![1.0, 2.0]` or `vector![0.0; n]`.
+#[macro_export]
+macro_rules! vector {
+    ($($x:expr),* $(,)?) => {
+        $crate::Vector::new($crate::__vec![$($x),*])
+    };
+    ($x:expr; $n:expr) => {
+        $crate::Vector::new($crate::__vec![$x; $n])
+    };
+}
+
+/// Builds a `Matrix` from nested rows: `matrix![[1.0, 2.0], [3.0, 4.0]]`.
+#[macro_export]
+macro_rules! matrix {
+    () => {
+        $crate::Matrix::new(0, 0, $crate::__vec![])
+    };
+    ($([$($x:expr),* $(,)?]),+ $(,)?) => {{
+        let rows = [$($crate::__vec![$($x),*]),+];
+        let cols = rows[0].len();
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "All matrix! rows must have the same length.",
+        );
+        $crate::Matrix::new(rows.len(), cols, rows.concat())
+    }};
+}
+
+/// Asserts that two matrices (or two vectors) have the same shape and are
+/// element-wise approximately equal, listing the differing positions on
+/// failure. Defaults to `relative_eq` with machine-epsilon tolerances; pass
+/// `epsilon = e`, `max_relative = r` or `max_ulps = n` to choose the
+/// comparison.
+#[macro_export]
+macro_rules! assert_matrix_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::approx::assert_eq_with(&$left, &$right, |a, b| {
+            $crate::approx::ApproxEq::relative_eq(
+                a,
+                b,
+                $crate::approx::ApproxEq::default_epsilon(),
+                $crate::approx::ApproxEq::default_max_relative(),
+            )
+        })
+    };
+    ($left:expr, $right:expr, epsilon = $epsilon:expr $(,)?) => {
+        $crate::approx::assert_eq_with(&$left, &$right, |a, b| {
+            $crate::approx::ApproxEq::abs_diff_eq(a, b, $epsilon)
+        })
+    };
+    ($left:expr, $right:expr, max_relative = $max_relative:expr $(,)?) => {
+        $crate::approx::assert_eq_with(&$left, &$right, |a, b| {
+            $crate::approx::ApproxEq::relative_eq(a, b, $crate::approx::ApproxEq::default_epsilon(), $max_relative)
+        })
+    };
+    ($left:expr, $right:expr, max_ulps = $max_ulps:expr $(,)?) => {
+        $crate::approx::assert_eq_with(&$left, &$right, |a, b| {
+            $crate::approx::ApproxEq::ulps_eq(a, b, $crate::approx::ApproxEq::default_epsilon(), $max_ulps)
+        })
+    };
+}
@@ -1,124 +1,210 @@
 use crate::vector::Vector;
-use std::ops::{Add, Sub, Mul, Index, IndexMut};
-
-
-#[derive(Debug, Clone)]
-pub struct Matrix<T> {
+use crate::simd::{self, Op};
+use crate::storage::{Storage, StorageMut};
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Add, Sub, Mul, Index, IndexMut};
+
+
+/// A row-major matrix whose elements live in `S`, a `Vec` by default; see
+/// `storage` for the other buffers that can back one.
+#[derive(Clone)]
+pub struct Matrix<T, S = Vec<T>> {
     pub rows: usize,
     pub cols: usize,
-    pub elements: Vec<T>,
+    pub elements: S,
+    element: PhantomData<T>,
 }
 
-impl<T> Matrix<T>
-where
-    T: Copy + Default,
-{
-    pub fn new(rows: usize, cols: usize, elements: Vec<T>) -> Self {
+impl<T, S: Storage<T>> Matrix<T, S> {
+    pub fn new(rows: usize, cols: usize, elements: S) -> Self {
         Matrix {
             rows,
             cols,
             elements,
+            element: PhantomData,
         }
     }
 
+    pub fn as_slice(&self) -> &[T] {
+        self.elements.as_slice()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T]
+    where
+        S: StorageMut<T>,
+    {
+        self.elements.as_mut_slice()
+    }
+
+    /// A matrix borrowing these elements.
+    pub fn view(&self) -> Matrix<T, &[T]> {
+        Matrix::new(self.rows, self.cols, self.as_slice())
+    }
+
+    /// Copies the elements into a `Vec`-backed matrix.
+    pub fn to_owned(&self) -> Matrix<T>
+    where
+        T: Clone,
+    {
+        Matrix::new(self.rows, self.cols, self.as_slice().to_vec())
+    }
+
+    pub fn transpose(&self) -> Matrix<T>
+    where
+        T: Clone,
+    {
+        Matrix::from_fn(self.cols, self.rows, |i, j| self[(j, i)].clone())
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T>
+    where
+        S: StorageMut<T>,
+    {
+        self.as_mut_slice().iter_mut()
+    }
+
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[T]> + '_ {
+        (0..self.rows).map(move |row| &self.as_slice()[row * self.cols..(row + 1) * self.cols])
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> + '_
+    where
+        S: StorageMut<T>,
+    {
+        // zero-column matrices have no elements to chunk, but still `rows` empty rows
+        let (rows, cols) = (self.rows, self.cols);
+        let empty = if cols == 0 { rows } else { 0 };
+
+        self.as_mut_slice()
+            .chunks_exact_mut(cols.max(1))
+            .chain(core::iter::repeat_with(<&mut [T]>::default).take(empty))
+    }
+
+    /// Iterates over columns, each yielded as an iterator down that column.
+    pub fn cols(&self) -> impl ExactSizeIterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.cols).map(move |col| self.as_slice().iter().skip(col).step_by(self.cols))
+    }
+
+}
+
+impl<T> Matrix<T> {
     pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
         let elements = (0..rows * cols)
             .map(|idx| f(idx / cols, idx % cols))
             .collect();
 
-        Matrix {
-            rows,
-            cols,
-            elements,
-        }
+        Matrix::new(rows, cols, elements)
     }
+}
 
-    pub fn transpose(&self) -> Self {
-        let mut elements = vec![T::default(); self.elements.len()];
+impl<T: fmt::Debug, S: Storage<T>> fmt::Debug for Matrix<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Matrix")
+            .field("rows", &self.rows)
+            .field("cols", &self.cols)
+            .field("elements", &self.as_slice())
+            .finish()
+    }
+}
 
-        for i in 0..self.rows {
-            for j in 0..self.cols {
-                elements[j * self.rows + i] = self[(i, j)];
-            }
-        }
+/// Equal shapes and elements, whatever the storage.
+impl<T: PartialEq, S: Storage<T>, S2: Storage<T>> PartialEq<Matrix<T, S2>> for Matrix<T, S> {
+    fn eq(&self, other: &Matrix<T, S2>) -> bool {
+        self.rows == other.rows && self.cols == other.cols && self.as_slice() == other.as_slice()
+    }
+}
 
-        Matrix {
-            rows: self.cols,
-            cols: self.rows,
-            elements,
-        }
+impl<'a, T, S: Storage<T>> IntoIterator for &'a Matrix<T, S> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
     }
+}
 
+impl<'a, T, S: StorageMut<T>> IntoIterator for &'a mut Matrix<T, S> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_mut_slice().iter_mut()
+    }
 }
 
-impl<T> Index<(usize, usize)> for Matrix<T> {
+impl<T, S: Storage<T>> Index<(usize, usize)> for Matrix<T, S> {
     type Output = T;
 
     fn index(&self, idx: (usize, usize)) -> &Self::Output {
-        &self.elements[idx.0 * self.cols + idx.1]
+        &self.as_slice()[idx.0 * self.cols + idx.1]
     }
 }
 
-impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+impl<T, S: StorageMut<T>> IndexMut<(usize, usize)> for Matrix<T, S> {
     fn index_mut(&mut self, idx: (usize, usize)) -> &mut Self::Output {
-        &mut self.elements[idx.0 * self.cols + idx.1]
+        let cols = self.cols;
+        &mut self.as_mut_slice()[idx.0 * cols + idx.1]
     }
 }
 
-impl<T> Add for &Matrix<T>
+impl<T, S, S2> Add<&Matrix<T, S2>> for &Matrix<T, S>
 where
-    T: Add<Output = T> + Copy,
+    T: Add<Output = T> + Copy + 'static,
+    S: Storage<T>,
+    S2: Storage<T>,
 {
     type Output = Matrix<T>;
 
-    fn add(self, other: Self) -> Matrix<T> {
+    fn add(self, other: &Matrix<T, S2>) -> Matrix<T> {
 
         assert_eq!(self.rows, other.rows, "Matrices must have same number of rows to add.");
         assert_eq!(self.cols, other.cols, "Matrices must have same number of columns to add.");
 
-        let elements = self.elements
-            .iter()
-            .zip(&other.elements)
-            .map(|(&a, &b)| a + b)
-            .collect();
+        let elements = simd::zip_with(self.as_slice(), other.as_slice(), Op::Add).unwrap_or_else(|| {
+            self.iter()
+                .zip(other)
+                .map(|(&a, &b)| a + b)
+                .collect()
+        });
 
-        Matrix {
-            rows: self.rows,
-            cols: self.cols,
-            elements,
-        }
+        Matrix::new(self.rows, self.cols, elements)
     }
 }
 
-impl<T> Sub for &Matrix<T>
+impl<T, S, S2> Sub<&Matrix<T, S2>> for &Matrix<T, S>
 where
-    T: Sub<Output = T> + Copy,
+    T: Sub<Output = T> + Copy + 'static,
+    S: Storage<T>,
+    S2: Storage<T>,
 {
     type Output = Matrix<T>;
 
-    fn sub(self, other: Self) -> Matrix<T> {
+    fn sub(self, other: &Matrix<T, S2>) -> Matrix<T> {
 
         assert_eq!(self.rows, other.rows, "Matrices must have the same number of rows to subtract.");
         assert_eq!(self.cols, other.cols, "Matrices must have the same number of columns to subtract.");
 
-        let elements = self
-            .elements
-            .iter()
-            .zip(&other.elements)
-            .map(|(&a, &b)| a - b)
-            .collect();
+        let elements = simd::zip_with(self.as_slice(), other.as_slice(), Op::Sub).unwrap_or_else(|| {
+            self.iter()
+                .zip(other)
+                .map(|(&a, &b)| a - b)
+                .collect()
+        });
 
-        Matrix {
-            rows: self.rows,
-            cols: self.cols,
-            elements,
-        }
+        Matrix::new(self.rows, self.cols, elements)
     }
 }
 
-impl<T> Mul<&Vector<T>> for &Matrix<T>
+impl<T, S> Mul<&Vector<T>> for &Matrix<T, S>
 where
-    T: Mul<Output = T> + Add<Output = T> + Copy + Default,
+    T: Mul<Output = T> + Add<Output = T> + Copy + Default + 'static,
+    S: Storage<T>,
 {
     type Output = Vector<T>;
 
@@ -129,30 +215,23 @@ where
             "Matrix columns must match vector size",
         );
 
-        let mut result_elements = Vec::with_capacity(self.rows);
-
-        for row in 0..self.rows {
-            let mut sum = T::default();
-
-            for col in 0..self.cols {
-                sum = sum + self[(row, col)] * vector[col];
-            }
-            result_elements.push(sum);
-        }
-
-        Vector::new(result_elements)
+        self.rows()
+            .map(|row| simd::dot(row, &vector.elements).unwrap_or_else(|| simd::scalar::dot(row, &vector.elements)))
+            .collect()
     }
 }
 
 
-impl<T> Mul for &Matrix<T>
+impl<T, S, S2> Mul<&Matrix<T, S2>> for &Matrix<T, S>
 where
-    T: Mul<Output = T> + Add<Output = T> + Copy + Default,
+    T: Mul<Output = T> + Add<Output = T> + Clone + Default,
+    S: Storage<T>,
+    S2: Storage<T>,
 {
     type Output = Matrix<T>;
 
-    fn mul(self, other: Self) -> Self::Output {
-        assert_eq!(self.cols, self.rows, "Matrix A columns must match Matrix B rows");
+    fn mul(self, other: &Matrix<T, S2>) -> Self::Output {
+        assert_eq!(self.cols, other.rows, "Matrix A columns must match Matrix B rows");
 
         let mut elements = Vec::with_capacity(self.rows * other.cols);
 
@@ -160,42 +239,32 @@ where
             for col in 0..other.cols {
                 let mut sum = T::default();
                 for k in 0..self.cols {
-                    sum = sum + self[(row, k)] * other[(k, col)];
+                    sum = sum + self[(row, k)].clone() * other[(k, col)].clone();
                 }
                 elements.push(sum);
             }
         }
 
-        Matrix {
-            rows: self.rows,
-            cols: self.cols,
-            elements,
-        }
+        Matrix::new(self.rows, other.cols, elements)
 
     }
 }
 
-impl<T> Mul<T> for &Matrix<T>
+impl<T, S> Mul<T> for &Matrix<T, S>
 where
     T: Mul<Output = T> + Copy,
+    S: Storage<T>,
 {
     type Output = Matrix<T>;
 
     fn mul(self, scalar: T) -> Self::Output {
 
-        let elements = self.elements
+        let elements = self
             .iter()
             .map(|&x| x * scalar)
             .collect();
 
-        Matrix {
-            rows: self.rows,
-            cols: self.cols,
-            elements,
-        }
+        Matrix::new(self.rows, self.cols, elements)
 
     }
 }
-
-
-
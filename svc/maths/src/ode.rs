@@ -0,0 +1,211 @@
+//! Initial value problems `y' = f(t, y)` with a `Vector<f64>` state.
+
+use crate::Vector;
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+
+#[derive(Debug, Clone, Default)]
+pub struct Trajectory {
+    pub t: Vec<f64>,
+    pub y: Vec<Vector<f64>>,
+}
+
+impl Trajectory {
+    /// The state at the end of the integration.
+    pub fn last(&self) -> &Vector<f64> {
+        self.y.last().expect("trajectory holds at least the initial state")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Error {
+    /// The step size needed to meet the tolerance fell below `min_step`.
+    StepTooSmall {
+        t: f64,
+    },
+    MaxSteps {
+        t: f64,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::StepTooSmall { t } => write!(f, "step size underflow at t = {}", t),
+            Error::MaxSteps { t } => write!(f, "step limit reached at t = {}", t),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+
+// y + h * Σ coefficient * k
+fn advance(y: &Vector<f64>, h: f64, terms: &[(f64, &Vector<f64>)]) -> Vector<f64> {
+    let mut next = y.clone();
+    for &(coefficient, k) in terms {
+        if coefficient != 0.0 {
+            next.axpy(h * coefficient, k);
+        }
+    }
+    next
+}
+
+/// One classic fourth-order Runge–Kutta step of size `h`.
+pub fn rk4_step(f: impl Fn(f64, &Vector<f64>) -> Vector<f64>, t: f64, y: &Vector<f64>, h: f64) -> Vector<f64> {
+    let k1 = f(t, y);
+    let k2 = f(t + 0.5 * h, &advance(y, h, &[(0.5, &k1)]));
+    let k3 = f(t + 0.5 * h, &advance(y, h, &[(0.5, &k2)]));
+    let k4 = f(t + h, &advance(y, h, &[(1.0, &k3)]));
+
+    advance(y, h, &[(1.0 / 6.0, &k1), (1.0 / 3.0, &k2), (1.0 / 3.0, &k3), (1.0 / 6.0, &k4)])
+}
+
+/// Integrates from `t0` to `t1` in `steps` fixed RK4 steps.
+pub fn rk4(
+    f: impl Fn(f64, &Vector<f64>) -> Vector<f64>,
+    t0: f64,
+    y0: &Vector<f64>,
+    t1: f64,
+    steps: usize,
+) -> Trajectory {
+    assert!(steps > 0, "RK4 needs at least one step.");
+
+    let h = (t1 - t0) / steps as f64;
+    let mut trajectory = Trajectory {
+        t: vec![t0],
+        y: vec![y0.clone()],
+    };
+
+    for step in 1..=steps {
+        let t = t0 + (step - 1) as f64 * h;
+        let next = rk4_step(&f, t, trajectory.last(), h);
+        trajectory.t.push(if step == steps { t1 } else { t0 + step as f64 * h });
+        trajectory.y.push(next);
+    }
+
+    trajectory
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub relative_tolerance: f64,
+    pub absolute_tolerance: f64,
+    /// First step to try; defaults to a hundredth of the interval.
+    pub initial_step: Option<f64>,
+    pub min_step: f64,
+    pub max_steps: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            relative_tolerance: 1e-6,
+            absolute_tolerance: 1e-9,
+            initial_step: None,
+            min_step: 1e-12,
+            max_steps: 100_000,
+        }
+    }
+}
+
+// Dormand–Prince 5(4) tableau
+const C: [f64; 6] = [1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0, 1.0];
+const A2: [f64; 1] = [1.0 / 5.0];
+const A3: [f64; 2] = [3.0 / 40.0, 9.0 / 40.0];
+const A4: [f64; 3] = [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0];
+const A5: [f64; 4] = [19372.0 / 6561.0, -25360.0 / 2187.0, 64448.0 / 6561.0, -212.0 / 729.0];
+const A6: [f64; 5] = [9017.0 / 3168.0, -355.0 / 33.0, 46732.0 / 5247.0, 49.0 / 176.0, -5103.0 / 18656.0];
+// fifth-order weights, which are also the last stage
+const B: [f64; 6] = [35.0 / 384.0, 0.0, 500.0 / 1113.0, 125.0 / 192.0, -2187.0 / 6784.0, 11.0 / 84.0];
+// fifth- minus fourth-order weights
+const E: [f64; 7] = [
+    71.0 / 57600.0,
+    0.0,
+    -71.0 / 16695.0,
+    71.0 / 1920.0,
+    -17253.0 / 339200.0,
+    22.0 / 525.0,
+    -1.0 / 40.0,
+];
+
+/// Adaptive Dormand–Prince 5(4) integration from `t0` to `t1`, recording
+/// every accepted step.
+pub fn dormand_prince(
+    f: impl Fn(f64, &Vector<f64>) -> Vector<f64>,
+    t0: f64,
+    y0: &Vector<f64>,
+    t1: f64,
+    options: &Options,
+) -> Result<Trajectory, Error> {
+    const SAFETY: f64 = 0.9;
+
+    let span = t1 - t0;
+    let direction = if span < 0.0 { -1.0 } else { 1.0 };
+    let mut h = options.initial_step.map_or(0.01 * span.abs(), f64::abs);
+
+    let mut trajectory = Trajectory {
+        t: vec![t0],
+        y: vec![y0.clone()],
+    };
+
+    let mut t = t0;
+    let mut y = y0.clone();
+    let mut k1 = f(t, &y);
+
+    for _ in 0..options.max_steps {
+        if (t1 - t) * direction <= 0.0 {
+            return Ok(trajectory);
+        }
+
+        h = h.min((t1 - t).abs());
+        if h < options.min_step && h < (t1 - t).abs() {
+            return Err(Error::StepTooSmall { t });
+        }
+        let dt = direction * h;
+
+        let k2 = f(t + C[0] * dt, &advance(&y, dt, &[(A2[0], &k1)]));
+        let k3 = f(t + C[1] * dt, &advance(&y, dt, &[(A3[0], &k1), (A3[1], &k2)]));
+        let k4 = f(t + C[2] * dt, &advance(&y, dt, &[(A4[0], &k1), (A4[1], &k2), (A4[2], &k3)]));
+        let k5 = f(t + C[3] * dt, &advance(&y, dt, &[(A5[0], &k1), (A5[1], &k2), (A5[2], &k3), (A5[3], &k4)]));
+        let k6 = f(
+            t + C[4] * dt,
+            &advance(&y, dt, &[(A6[0], &k1), (A6[1], &k2), (A6[2], &k3), (A6[3], &k4), (A6[4], &k5)]),
+        );
+        let next = advance(&y, dt, &[(B[0], &k1), (B[2], &k3), (B[3], &k4), (B[4], &k5), (B[5], &k6)]);
+        let k7 = f(t + C[5] * dt, &next);
+
+        let stages = [&k1, &k2, &k3, &k4, &k5, &k6, &k7];
+        let sum_of_squares: f64 = (0..y.len())
+            .map(|i| {
+                let error: f64 = stages.iter().zip(&E).map(|(k, e)| e * k[i]).sum::<f64>() * dt;
+                let scale = options.absolute_tolerance + options.relative_tolerance * y[i].abs().max(next[i].abs());
+                (error / scale).powi(2)
+            })
+            .sum();
+        let error = (sum_of_squares / y.len().max(1) as f64).sqrt();
+
+        let factor = if error == 0.0 { 5.0 } else { (SAFETY * error.powf(-0.2)).clamp(0.2, 5.0) };
+
+        if error <= 1.0 {
+            t = if (t1 - t).abs() <= h { t1 } else { t + dt };
+            y = next;
+            k1 = k7;
+            trajectory.t.push(t);
+            trajectory.y.push(y.clone());
+        }
+
+        h *= factor;
+    }
+
+    if (t1 - t) * direction <= 0.0 {
+        Ok(trajectory)
+    } else {
+        Err(Error::MaxSteps { t })
+    }
+}
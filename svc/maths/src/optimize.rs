@@ -0,0 +1,541 @@
+//! Minimisers for objectives over `Vector<f64>` parameters.
+//!
+//! The gradient-based methods share one driver: each picks a search
+//! direction and a backtracking line search enforces sufficient decrease.
+//! Objectives without an analytic gradient get one from `diff`.
+
+use crate::{diff, Matrix, Vector};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use alloc::{collections::VecDeque, vec::Vec};
+
+
+pub trait Objective {
+    fn value(&self, x: &Vector<f64>) -> f64;
+
+    /// Analytic gradient, if known.
+    fn gradient(&self, _x: &Vector<f64>) -> Option<Vector<f64>> {
+        None
+    }
+}
+
+impl<F: Fn(&Vector<f64>) -> f64> Objective for F {
+    fn value(&self, x: &Vector<f64>) -> f64 {
+        self(x)
+    }
+}
+
+/// Pairs a value closure with its analytic gradient.
+pub struct WithGradient<F, G> {
+    pub value: F,
+    pub gradient: G,
+}
+
+impl<F, G> Objective for WithGradient<F, G>
+where
+    F: Fn(&Vector<f64>) -> f64,
+    G: Fn(&Vector<f64>) -> Vector<f64>,
+{
+    fn value(&self, x: &Vector<f64>) -> f64 {
+        (self.value)(x)
+    }
+
+    fn gradient(&self, x: &Vector<f64>) -> Option<Vector<f64>> {
+        Some((self.gradient)(x))
+    }
+}
+
+/// A nonlinear least-squares problem, minimising half the squared norm of
+/// the residuals.
+pub trait LeastSquares {
+    fn residuals(&self, x: &Vector<f64>) -> Vector<f64>;
+
+    /// Analytic Jacobian of the residuals, one row per residual, if known.
+    fn jacobian(&self, _x: &Vector<f64>) -> Option<Matrix<f64>> {
+        None
+    }
+}
+
+impl<F: Fn(&Vector<f64>) -> Vector<f64>> LeastSquares for F {
+    fn residuals(&self, x: &Vector<f64>) -> Vector<f64> {
+        self(x)
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub max_iterations: usize,
+    /// Converged once the gradient norm is at most this.
+    pub gradient_tolerance: f64,
+    /// Converged once an iteration improves the value by at most this,
+    /// relative to the value (or absolutely below 1).
+    pub value_tolerance: f64,
+    /// Converged once a step moves the parameters by at most this, relative
+    /// to their norm (or absolutely below 1).
+    pub step_tolerance: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            max_iterations: 1000,
+            gradient_tolerance: 1e-8,
+            value_tolerance: 1e-14,
+            step_tolerance: 1e-12,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Converged,
+    MaxIterations,
+    /// No step along the search direction reduced the objective.
+    Stalled,
+}
+
+#[derive(Debug, Clone)]
+pub struct Minimum {
+    pub x: Vector<f64>,
+    pub value: f64,
+    pub iterations: usize,
+    pub status: Status,
+    pub gradient_norm: f64,
+}
+
+impl Minimum {
+    pub fn converged(&self) -> bool {
+        self.status == Status::Converged
+    }
+}
+
+
+fn norm(v: &Vector<f64>) -> f64 {
+    v.dot(v).sqrt()
+}
+
+fn scaled(v: &Vector<f64>, alpha: f64) -> Vector<f64> {
+    v * alpha
+}
+
+fn within(change: f64, size: f64, tolerance: f64) -> bool {
+    change <= tolerance * size.max(1.0)
+}
+
+fn gradient_of<O: Objective + ?Sized>(objective: &O, x: &Vector<f64>) -> Vector<f64> {
+    objective.gradient(x).unwrap_or_else(|| diff::gradient(|x| objective.value(x), x))
+}
+
+// backtracking until the Armijo sufficient-decrease condition holds
+fn line_search<O: Objective + ?Sized>(
+    objective: &O,
+    x: &Vector<f64>,
+    value: f64,
+    gradient: &Vector<f64>,
+    direction: &Vector<f64>,
+    initial_step: f64,
+) -> Option<(f64, Vector<f64>, f64)> {
+    const ARMIJO: f64 = 1e-4;
+
+    let slope = gradient.dot(direction);
+    if slope >= 0.0 || !slope.is_finite() {
+        return None;
+    }
+
+    let mut step = initial_step;
+    for _ in 0..64 {
+        let mut candidate = x.clone();
+        candidate.axpy(step, direction);
+        let candidate_value = objective.value(&candidate);
+
+        if candidate_value <= value + ARMIJO * step * slope {
+            return Some((step, candidate, candidate_value));
+        }
+        step *= 0.5;
+    }
+
+    None
+}
+
+
+trait Direction {
+    /// Whether a step of 1 along `direction` is already well scaled.
+    const UNIT_STEP: bool;
+
+    fn direction(&mut self, gradient: &Vector<f64>) -> Vector<f64>;
+    /// Records an accepted step `s` and the resulting gradient change `y`.
+    fn update(&mut self, s: Vector<f64>, y: Vector<f64>);
+    /// Forgets any history, falling back to steepest descent.
+    fn reset(&mut self);
+}
+
+struct SteepestDescent;
+
+impl Direction for SteepestDescent {
+    const UNIT_STEP: bool = false;
+
+    fn direction(&mut self, gradient: &Vector<f64>) -> Vector<f64> {
+        scaled(gradient, -1.0)
+    }
+
+    fn update(&mut self, _s: Vector<f64>, _y: Vector<f64>) {}
+
+    fn reset(&mut self) {}
+}
+
+// Polak–Ribière with the PR+ restart rule
+#[derive(Default)]
+struct ConjugateGradient {
+    previous: Option<(Vector<f64>, Vector<f64>)>,
+    direction: Option<Vector<f64>>,
+}
+
+impl Direction for ConjugateGradient {
+    const UNIT_STEP: bool = false;
+
+    fn direction(&mut self, gradient: &Vector<f64>) -> Vector<f64> {
+        let mut direction = scaled(gradient, -1.0);
+
+        if let (Some((_, y)), Some(previous_direction)) = (&self.previous, &self.direction) {
+            let previous_gradient = gradient - y;
+            let beta = (gradient.dot(y) / previous_gradient.dot(&previous_gradient)).max(0.0);
+            if beta.is_finite() {
+                direction.axpy(beta, previous_direction);
+            }
+        }
+
+        self.direction = Some(direction.clone());
+        direction
+    }
+
+    fn update(&mut self, s: Vector<f64>, y: Vector<f64>) {
+        self.previous = Some((s, y));
+    }
+
+    fn reset(&mut self) {
+        self.previous = None;
+        self.direction = None;
+    }
+}
+
+struct Lbfgs {
+    memory: usize,
+    // (s, y, 1 / y·s), oldest first
+    history: VecDeque<(Vector<f64>, Vector<f64>, f64)>,
+}
+
+impl Direction for Lbfgs {
+    const UNIT_STEP: bool = true;
+
+    fn direction(&mut self, gradient: &Vector<f64>) -> Vector<f64> {
+        let mut q = gradient.clone();
+        let mut alphas = Vec::with_capacity(self.history.len());
+
+        for (s, y, rho) in self.history.iter().rev() {
+            let alpha = rho * s.dot(&q);
+            q.axpy(-alpha, y);
+            alphas.push(alpha);
+        }
+
+        let gamma = self.history
+            .back()
+            .map_or(1.0 / norm(gradient).max(1.0), |(s, y, _)| s.dot(y) / y.dot(y));
+        let mut r = scaled(&q, gamma);
+
+        for ((s, y, rho), alpha) in self.history.iter().zip(alphas.into_iter().rev()) {
+            let beta = rho * y.dot(&r);
+            r.axpy(alpha - beta, s);
+        }
+
+        scaled(&r, -1.0)
+    }
+
+    fn update(&mut self, s: Vector<f64>, y: Vector<f64>) {
+        let curvature = s.dot(&y);
+
+        // skipping pairs without positive curvature keeps the implied Hessian positive definite
+        if curvature > f64::EPSILON * norm(&y) * norm(&s) {
+            if self.history.len() == self.memory {
+                self.history.pop_front();
+            }
+            self.history.push_back((s, y, 1.0 / curvature));
+        }
+    }
+
+    fn reset(&mut self) {
+        self.history.clear();
+    }
+}
+
+fn descend<O, D>(objective: &O, x0: &Vector<f64>, options: &Options, mut search: D) -> Minimum
+where
+    O: Objective + ?Sized,
+    D: Direction,
+{
+    let mut x = x0.clone();
+    let mut value = objective.value(&x);
+    let mut gradient = gradient_of(objective, &x);
+    let mut status = Status::MaxIterations;
+    let mut iterations = 0;
+    let mut last_step = 0.5 / norm(&gradient).max(1.0);
+
+    while iterations < options.max_iterations {
+        if norm(&gradient) <= options.gradient_tolerance {
+            status = Status::Converged;
+            break;
+        }
+
+        let direction = search.direction(&gradient);
+        let initial_step = if D::UNIT_STEP { 1.0 } else { 2.0 * last_step };
+
+        let accepted = line_search(objective, &x, value, &gradient, &direction, initial_step)
+            .or_else(|| {
+                search.reset();
+                let steepest = scaled(&gradient, -1.0);
+                line_search(objective, &x, value, &gradient, &steepest, 1.0 / norm(&gradient))
+            });
+
+        let Some((step, next, next_value)) = accepted else {
+            status = Status::Stalled;
+            break;
+        };
+
+        iterations += 1;
+        last_step = step;
+
+        let next_gradient = gradient_of(objective, &next);
+        let s = &next - &x;
+        let y = &next_gradient - &gradient;
+        let (moved, improvement) = (norm(&s), value - next_value);
+
+        search.update(s, y);
+        x = next;
+        value = next_value;
+        gradient = next_gradient;
+
+        if within(improvement, value.abs(), options.value_tolerance)
+            || within(moved, norm(&x), options.step_tolerance)
+        {
+            status = Status::Converged;
+            break;
+        }
+    }
+
+    Minimum {
+        gradient_norm: norm(&gradient),
+        x,
+        value,
+        iterations,
+        status,
+    }
+}
+
+
+pub fn gradient_descent<O: Objective + ?Sized>(objective: &O, x0: &Vector<f64>, options: &Options) -> Minimum {
+    descend(objective, x0, options, SteepestDescent)
+}
+
+/// Nonlinear conjugate gradient (Polak–Ribière+).
+pub fn conjugate_gradient<O: Objective + ?Sized>(objective: &O, x0: &Vector<f64>, options: &Options) -> Minimum {
+    descend(objective, x0, options, ConjugateGradient::default())
+}
+
+/// Limited-memory BFGS keeping the last `memory` steps.
+pub fn lbfgs<O: Objective + ?Sized>(objective: &O, x0: &Vector<f64>, memory: usize, options: &Options) -> Minimum {
+    assert!(memory > 0, "L-BFGS needs a memory of at least one step.");
+
+    descend(objective, x0, options, Lbfgs {
+        memory,
+        history: VecDeque::with_capacity(memory),
+    })
+}
+
+/// Derivative-free downhill simplex. Converges once the spread of values or
+/// the size of the simplex falls within tolerance; the reported gradient norm
+/// is measured at the final point.
+pub fn nelder_mead<O: Objective + ?Sized>(objective: &O, x0: &Vector<f64>, options: &Options) -> Minimum {
+    const REFLECT: f64 = 1.0;
+    const EXPAND: f64 = 2.0;
+    const CONTRACT: f64 = 0.5;
+    const SHRINK: f64 = 0.5;
+
+    let n = x0.len();
+    assert!(n > 0, "Nelder–Mead needs at least one parameter.");
+
+    let mut simplex: Vec<(Vector<f64>, f64)> = Vec::with_capacity(n + 1);
+    simplex.push((x0.clone(), objective.value(x0)));
+
+    for i in 0..n {
+        let mut vertex = x0.clone();
+        vertex[i] = if x0[i] == 0.0 { 0.00025 } else { 1.05 * x0[i] };
+        let value = objective.value(&vertex);
+        simplex.push((vertex, value));
+    }
+
+    let mut status = Status::MaxIterations;
+    let mut iterations = 0;
+
+    // a point `t` of the way from the centroid towards (or beyond, for t < 0) the worst vertex
+    let along = |centroid: &Vector<f64>, worst: &Vector<f64>, t: f64| {
+        let mut point = centroid.clone();
+        point.axpy(t, &(worst - centroid));
+        point
+    };
+
+    while iterations < options.max_iterations {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let (best, best_value) = (&simplex[0].0, simplex[0].1);
+        let spread = simplex.iter().map(|(_, v)| (v - best_value).abs()).fold(0.0, f64::max);
+        let size = simplex.iter().map(|(x, _)| norm(&(x - best))).fold(0.0, f64::max);
+
+        if within(spread, best_value.abs(), options.value_tolerance)
+            || within(size, norm(best), options.step_tolerance)
+        {
+            status = Status::Converged;
+            break;
+        }
+
+        iterations += 1;
+
+        let mut centroid = Vector::from_fn(n, |_| 0.0);
+        for (vertex, _) in &simplex[..n] {
+            centroid.axpy(1.0 / n as f64, vertex);
+        }
+
+        let worst = simplex[n].0.clone();
+        let worst_value = simplex[n].1;
+        let second_worst_value = simplex[n - 1].1;
+
+        let reflected = along(&centroid, &worst, -REFLECT);
+        let reflected_value = objective.value(&reflected);
+
+        if reflected_value < best_value {
+            let expanded = along(&centroid, &worst, -EXPAND);
+            let expanded_value = objective.value(&expanded);
+            simplex[n] = if expanded_value < reflected_value {
+                (expanded, expanded_value)
+            } else {
+                (reflected, reflected_value)
+            };
+            continue;
+        }
+
+        if reflected_value < second_worst_value {
+            simplex[n] = (reflected, reflected_value);
+            continue;
+        }
+
+        // contract towards the better of the reflected and worst points
+        let (contracted, contracted_value) = if reflected_value < worst_value {
+            let point = along(&centroid, &worst, -CONTRACT);
+            let value = objective.value(&point);
+            (point, value)
+        } else {
+            let point = along(&centroid, &worst, CONTRACT);
+            let value = objective.value(&point);
+            (point, value)
+        };
+
+        if contracted_value < reflected_value.min(worst_value) {
+            simplex[n] = (contracted, contracted_value);
+            continue;
+        }
+
+        let best = simplex[0].0.clone();
+        for (vertex, value) in simplex.iter_mut().skip(1) {
+            *vertex = along(&best, vertex, SHRINK);
+            *value = objective.value(vertex);
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let (x, value) = simplex.swap_remove(0);
+
+    Minimum {
+        gradient_norm: norm(&gradient_of(objective, &x)),
+        x,
+        value,
+        iterations,
+        status,
+    }
+}
+
+/// Levenberg–Marquardt for nonlinear least squares. The reported value is
+/// half the squared residual norm and the gradient is `Jᵀ r`.
+pub fn levenberg_marquardt<P: LeastSquares + ?Sized>(problem: &P, x0: &Vector<f64>, options: &Options) -> Minimum {
+    const MAX_DAMPING: f64 = 1e16;
+
+    let mut x = x0.clone();
+    let mut residuals = problem.residuals(&x);
+    let mut value = 0.5 * residuals.dot(&residuals);
+    let mut damping = 1e-3;
+    let mut status = Status::MaxIterations;
+    let mut iterations = 0;
+    let mut settled = false;
+    let mut gradient_norm;
+
+    loop {
+        let jacobian = problem.jacobian(&x).unwrap_or_else(|| diff::jacobian(|x| problem.residuals(x), &x));
+        let transposed = jacobian.transpose();
+        let gradient = &transposed * &residuals;
+        gradient_norm = norm(&gradient);
+
+        if settled || gradient_norm <= options.gradient_tolerance {
+            status = Status::Converged;
+            break;
+        }
+        if iterations == options.max_iterations {
+            break;
+        }
+
+        let normal = &transposed * &jacobian;
+        let rhs = scaled(&gradient, -1.0);
+        let mut accepted = None;
+
+        while damping <= MAX_DAMPING {
+            // Marquardt's scaling damps each parameter by its own curvature
+            let mut damped = normal.clone();
+            for i in 0..x.len() {
+                damped[(i, i)] += damping * normal[(i, i)].max(f64::EPSILON);
+            }
+
+            if let Some(delta) = damped.solve(&rhs) {
+                let candidate = &x + &delta;
+                let candidate_residuals = problem.residuals(&candidate);
+                let candidate_value = 0.5 * candidate_residuals.dot(&candidate_residuals);
+
+                if candidate_value < value {
+                    damping = (damping / 10.0).max(1e-12);
+                    accepted = Some((candidate, candidate_residuals, candidate_value, norm(&delta)));
+                    break;
+                }
+            }
+
+            damping *= 10.0;
+        }
+
+        let Some((next, next_residuals, next_value, moved)) = accepted else {
+            status = Status::Stalled;
+            break;
+        };
+
+        iterations += 1;
+        let improvement = value - next_value;
+        x = next;
+        residuals = next_residuals;
+        value = next_value;
+
+        settled = within(improvement, value, options.value_tolerance)
+            || within(moved, norm(&x), options.step_tolerance);
+    }
+
+    Minimum {
+        x,
+        value,
+        iterations,
+        status,
+        gradient_norm,
+    }
+}
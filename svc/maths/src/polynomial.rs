@@ -0,0 +1,219 @@
+//! Polynomials over a `Field`, stored as coefficients in ascending powers,
+//! `c[0] + c[1] x + c[2] x² + ...`, the same order `interp::polyfit` returns.
+
+use crate::{Field, Vector};
+#[cfg(feature = "num")]
+use crate::Matrix;
+use alloc::vec;
+#[cfg(feature = "num")]
+use alloc::vec::Vec;
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+
+/// Trailing zero coefficients are dropped, so the zero polynomial has no
+/// coefficients at all.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial<T> {
+    coefficients: Vector<T>,
+}
+
+impl<T: Field> Polynomial<T> {
+
+    pub fn new(coefficients: Vector<T>) -> Self {
+        let mut coefficients = coefficients;
+        while coefficients.elements.last().is_some_and(|c| *c == T::zero()) {
+            coefficients.elements.pop();
+        }
+
+        Polynomial { coefficients }
+    }
+
+    pub fn zero() -> Self {
+        Polynomial::new(Vector::new(vec![]))
+    }
+
+    pub fn constant(c: T) -> Self {
+        Polynomial::new(Vector::new(vec![c]))
+    }
+
+    /// The monic polynomial `(x - r₀)(x - r₁)...` with the given roots.
+    pub fn from_roots(roots: &[T]) -> Self {
+        roots.iter().fold(Polynomial::constant(T::one()), |acc, root| {
+            &acc * &Polynomial::new(Vector::new(vec![-root.clone(), T::one()]))
+        })
+    }
+
+    pub fn coefficients(&self) -> &Vector<T> {
+        &self.coefficients
+    }
+
+    /// `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    pub fn leading_coefficient(&self) -> Option<&T> {
+        self.coefficients.last()
+    }
+
+    /// Horner's scheme.
+    pub fn eval(&self, x: &T) -> T {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(T::zero(), |acc, c| acc * x.clone() + c.clone())
+    }
+
+    pub fn derivative(&self) -> Self {
+        let mut k = T::zero();
+        let coefficients = self.coefficients
+            .iter()
+            .skip(1)
+            .map(|c| {
+                k = k.clone() + T::one();
+                k.clone() * c.clone()
+            })
+            .collect();
+
+        Polynomial::new(coefficients)
+    }
+
+    /// The antiderivative with a zero constant term.
+    pub fn integral(&self) -> Self {
+        let mut k = T::zero();
+        let coefficients = core::iter::once(T::zero())
+            .chain(self.coefficients.iter().map(|c| {
+                k = k.clone() + T::one();
+                c.clone() / k.clone()
+            }))
+            .collect();
+
+        Polynomial::new(coefficients)
+    }
+
+    /// Long division, returning the quotient and remainder.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let divisor_degree = divisor.degree().expect("Polynomial division by zero.");
+        let lead = divisor.coefficients[divisor_degree].clone();
+
+        let mut remainder = self.coefficients.elements.clone();
+        let Some(quotient_len) = remainder.len().checked_sub(divisor_degree).filter(|&len| len > 0) else {
+            return (Polynomial::zero(), self.clone());
+        };
+        let mut quotient = vec![T::zero(); quotient_len];
+
+        for power in (0..quotient_len).rev() {
+            let factor = remainder[power + divisor_degree].clone() / lead.clone();
+            for (i, d) in divisor.coefficients.iter().enumerate() {
+                remainder[power + i] = remainder[power + i].clone() - factor.clone() * d.clone();
+            }
+            quotient[power] = factor;
+        }
+
+        remainder.truncate(divisor_degree);
+
+        (Polynomial::new(Vector::new(quotient)), Polynomial::new(Vector::new(remainder)))
+    }
+
+}
+
+#[cfg(feature = "num")]
+impl Polynomial<f64> {
+
+    /// Complex roots, with multiplicity, as the eigenvalues of the companion
+    /// matrix. `None` for constant polynomials or if the eigenvalue
+    /// iteration fails.
+    pub fn roots(&self) -> Option<Vec<num::complex::Complex64>> {
+        let degree = self.degree().filter(|&d| d > 0)?;
+        let lead = self.coefficients[degree];
+
+        let companion = Matrix::from_fn(degree, degree, |row, col| {
+            if row == 0 {
+                -self.coefficients[degree - 1 - col] / lead
+            } else if row == col + 1 {
+                1.0
+            } else {
+                0.0
+            }
+        });
+
+        companion.eigenvalues()
+    }
+
+}
+
+fn zip_coefficients<T: Field>(a: &Polynomial<T>, b: &Polynomial<T>, op: impl Fn(T, T) -> T) -> Polynomial<T> {
+    let len = a.coefficients.len().max(b.coefficients.len());
+    let at = |p: &Polynomial<T>, i: usize| p.coefficients.get(i).cloned().unwrap_or_else(T::zero);
+
+    Polynomial::new((0..len).map(|i| op(at(a, i), at(b, i))).collect())
+}
+
+impl<T: Field> Add for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn add(self, other: Self) -> Polynomial<T> {
+        zip_coefficients(self, other, |a, b| a + b)
+    }
+}
+
+impl<T: Field> Sub for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn sub(self, other: Self) -> Polynomial<T> {
+        zip_coefficients(self, other, |a, b| a - b)
+    }
+}
+
+impl<T: Field> Neg for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn neg(self) -> Polynomial<T> {
+        Polynomial::new(self.coefficients.iter().map(|c| -c.clone()).collect())
+    }
+}
+
+impl<T: Field> Mul for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn mul(self, other: Self) -> Polynomial<T> {
+        if self.is_zero() || other.is_zero() {
+            return Polynomial::zero();
+        }
+
+        let mut product = vec![T::zero(); self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                product[i + j] = product[i + j].clone() + a.clone() * b.clone();
+            }
+        }
+
+        Polynomial::new(Vector::new(product))
+    }
+}
+
+impl<T: Field> Div for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn div(self, divisor: Self) -> Polynomial<T> {
+        self.div_rem(divisor).0
+    }
+}
+
+impl<T: Field> Rem for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn rem(self, divisor: Self) -> Polynomial<T> {
+        self.div_rem(divisor).1
+    }
+}
+
+impl<T: Field> From<Vector<T>> for Polynomial<T> {
+    fn from(coefficients: Vector<T>) -> Self {
+        Polynomial::new(coefficients)
+    }
+}
@@ -0,0 +1,151 @@
+//! Definite integrals of `f64` functions over finite intervals.
+
+use alloc::{vec, vec::Vec};
+
+
+/// Composite trapezoid rule over `n` equal panels.
+pub fn trapezoid(f: impl Fn(f64) -> f64, a: f64, b: f64, n: usize) -> f64 {
+    assert!(n > 0, "The trapezoid rule needs at least one panel.");
+
+    let h = (b - a) / n as f64;
+    let interior: f64 = (1..n).map(|i| f(a + i as f64 * h)).sum();
+
+    h * (0.5 * (f(a) + f(b)) + interior)
+}
+
+/// Composite Simpson's rule over `n` equal panels, rounded up to an even
+/// number.
+pub fn simpson(f: impl Fn(f64) -> f64, a: f64, b: f64, n: usize) -> f64 {
+    let n = (n.max(2) + 1) & !1;
+    let h = (b - a) / n as f64;
+
+    let interior: f64 = (1..n)
+        .map(|i| if i % 2 == 1 { 4.0 } else { 2.0 } * f(a + i as f64 * h))
+        .sum();
+
+    h / 3.0 * (f(a) + f(b) + interior)
+}
+
+
+// non-negative Kronrod nodes on [-1, 1]; the odd-indexed ones are also the 7-point Gauss nodes
+const KRONROD_NODES: [f64; 8] = [
+    0.991_455_371_120_812_6,
+    0.949_107_912_342_758_5,
+    0.864_864_423_359_769_1,
+    0.741_531_185_599_394_5,
+    0.586_087_235_467_691_1,
+    0.405_845_151_377_397_2,
+    0.207_784_955_007_898_48,
+    0.0,
+];
+
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022_935_322_010_529_224,
+    0.063_092_092_629_978_56,
+    0.104_790_010_322_250_19,
+    0.140_653_259_715_525_92,
+    0.169_004_726_639_267_9,
+    0.190_350_578_064_785_42,
+    0.204_432_940_075_298_89,
+    0.209_482_141_084_727_82,
+];
+
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.129_484_966_168_869_7,
+    0.279_705_391_489_276_64,
+    0.381_830_050_505_118_9,
+    0.417_959_183_673_469_4,
+];
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub absolute_tolerance: f64,
+    pub relative_tolerance: f64,
+    pub max_intervals: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            absolute_tolerance: 1e-10,
+            relative_tolerance: 1e-10,
+            max_intervals: 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Integral {
+    pub value: f64,
+    /// Estimated absolute error.
+    pub error: f64,
+    pub evaluations: usize,
+    /// Whether `error` is within the requested tolerance.
+    pub converged: bool,
+}
+
+// (Kronrod estimate, |Kronrod - Gauss|) over one interval
+fn kronrod_15(f: &impl Fn(f64) -> f64, a: f64, b: f64) -> (f64, f64) {
+    let centre = 0.5 * (a + b);
+    let half = 0.5 * (b - a);
+
+    let mid = f(centre);
+    let mut kronrod = KRONROD_WEIGHTS[7] * mid;
+    let mut gauss = GAUSS_WEIGHTS[3] * mid;
+
+    for (i, (&node, &weight)) in KRONROD_NODES[..7].iter().zip(&KRONROD_WEIGHTS).enumerate() {
+        let pair = f(centre - half * node) + f(centre + half * node);
+        kronrod += weight * pair;
+        if i % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[i / 2] * pair;
+        }
+    }
+
+    (kronrod * half, ((kronrod - gauss) * half).abs())
+}
+
+/// Globally adaptive 7–15 point Gauss–Kronrod quadrature: the interval with
+/// the largest error estimate is bisected until the total error is within
+/// tolerance or `max_intervals` is reached.
+pub fn gauss_kronrod(f: impl Fn(f64) -> f64, a: f64, b: f64, options: &Options) -> Integral {
+    const EVALUATIONS: usize = 15;
+
+    let (value, error) = kronrod_15(&f, a, b);
+    let mut intervals = vec![(a, b, value, error)];
+    let mut evaluations = EVALUATIONS;
+
+    loop {
+        let value: f64 = intervals.iter().map(|i| i.2).sum();
+        let error: f64 = intervals.iter().map(|i| i.3).sum();
+        let converged = error <= options.absolute_tolerance.max(options.relative_tolerance * value.abs());
+
+        if converged || intervals.len() >= options.max_intervals {
+            return Integral {
+                value,
+                error,
+                evaluations,
+                converged,
+            };
+        }
+
+        let worst = intervals
+            .iter()
+            .enumerate()
+            .max_by(|x, y| x.1.3.total_cmp(&y.1.3))
+            .map(|(idx, _)| idx)
+            .unwrap();
+
+        let (lo, hi, _, _) = intervals.swap_remove(worst);
+        let mid = 0.5 * (lo + hi);
+        let halves: Vec<_> = [(lo, mid), (mid, hi)]
+            .into_iter()
+            .map(|(lo, hi)| {
+                let (value, error) = kronrod_15(&f, lo, hi);
+                (lo, hi, value, error)
+            })
+            .collect();
+
+        intervals.extend(halves);
+        evaluations += 2 * EVALUATIONS;
+    }
+}
@@ -0,0 +1,237 @@
+//! Root finding for scalar functions and for systems of equations.
+
+use crate::{Matrix, Vector};
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::Float;
+use core::fmt;
+
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub max_iterations: usize,
+    /// Converged once the bracket or step is at most this, relative to the
+    /// root (or absolutely below 1).
+    pub x_tolerance: f64,
+    /// Converged once `|f(x)|` (or its norm, for systems) is at most this.
+    pub f_tolerance: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            max_iterations: 100,
+            x_tolerance: 1e-12,
+            f_tolerance: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Root<X> {
+    pub x: X,
+    pub iterations: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Error {
+    /// The function has the same sign at both ends of the interval.
+    NotBracketed,
+    ZeroDerivative {
+        x: f64,
+    },
+    SingularJacobian,
+    NoConvergence {
+        iterations: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotBracketed => write!(f, "interval does not bracket a root"),
+            Error::ZeroDerivative { x } => write!(f, "derivative vanished at {}", x),
+            Error::SingularJacobian => write!(f, "jacobian is singular"),
+            Error::NoConvergence { iterations } => write!(f, "no convergence after {} iterations", iterations),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+
+fn bracketed(fa: f64, fb: f64) -> bool {
+    (fa <= 0.0 && fb >= 0.0) || (fa >= 0.0 && fb <= 0.0)
+}
+
+fn small(step: f64, x: f64, tolerance: f64) -> bool {
+    step.abs() <= tolerance * x.abs().max(1.0)
+}
+
+pub fn bisection(f: impl Fn(f64) -> f64, a: f64, b: f64, options: &Options) -> Result<Root<f64>> {
+    let (mut a, mut b) = (a, b);
+    let (mut fa, fb) = (f(a), f(b));
+
+    if !bracketed(fa, fb) {
+        return Err(Error::NotBracketed);
+    }
+    if fb == 0.0 {
+        return Ok(Root { x: b, iterations: 0 });
+    }
+
+    for iteration in 1..=options.max_iterations {
+        let mid = 0.5 * (a + b);
+        let fm = f(mid);
+
+        if fm.abs() <= options.f_tolerance || small(0.5 * (b - a), mid, options.x_tolerance) {
+            return Ok(Root { x: mid, iterations: iteration });
+        }
+
+        if bracketed(fa, fm) {
+            b = mid;
+        } else {
+            a = mid;
+            fa = fm;
+        }
+    }
+
+    Err(Error::NoConvergence { iterations: options.max_iterations })
+}
+
+/// Brent's method: inverse quadratic interpolation and secant steps,
+/// falling back to bisection so the bracket always shrinks.
+pub fn brent(f: impl Fn(f64) -> f64, a: f64, b: f64, options: &Options) -> Result<Root<f64>> {
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+
+    if !bracketed(fa, fb) {
+        return Err(Error::NotBracketed);
+    }
+
+    let (mut c, mut fc) = (b, fb);
+    let (mut d, mut e) = (b - a, b - a);
+
+    for iteration in 1..=options.max_iterations {
+        if (fb > 0.0) == (fc > 0.0) {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        // keep b as the best estimate, with the root between b and c
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tolerance = 2.0 * f64::EPSILON * b.abs() + 0.5 * options.x_tolerance * b.abs().max(1.0);
+        let half = 0.5 * (c - b);
+
+        if half.abs() <= tolerance || fb.abs() <= options.f_tolerance {
+            return Ok(Root { x: b, iterations: iteration });
+        }
+
+        if e.abs() >= tolerance && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (p, q) = if a == c {
+                (2.0 * half * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * half * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            let (p, q) = if p > 0.0 { (p, -q) } else { (-p, q) };
+
+            if 2.0 * p < (3.0 * half * q - (tolerance * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = half;
+                e = d;
+            }
+        } else {
+            d = half;
+            e = d;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tolerance {
+            d
+        } else if half > 0.0 {
+            tolerance
+        } else {
+            -tolerance
+        };
+        fb = f(b);
+    }
+
+    Err(Error::NoConvergence { iterations: options.max_iterations })
+}
+
+pub fn newton(
+    f: impl Fn(f64) -> f64,
+    derivative: impl Fn(f64) -> f64,
+    x0: f64,
+    options: &Options,
+) -> Result<Root<f64>> {
+    let mut x = x0;
+
+    for iteration in 1..=options.max_iterations {
+        let fx = f(x);
+        if fx.abs() <= options.f_tolerance {
+            return Ok(Root { x, iterations: iteration - 1 });
+        }
+
+        let slope = derivative(x);
+        if slope == 0.0 || !slope.is_finite() {
+            return Err(Error::ZeroDerivative { x });
+        }
+
+        let step = fx / slope;
+        x -= step;
+
+        if small(step, x, options.x_tolerance) {
+            return Ok(Root { x, iterations: iteration });
+        }
+    }
+
+    Err(Error::NoConvergence { iterations: options.max_iterations })
+}
+
+/// Newton's method for `f(x) = 0` with `f: Rⁿ → Rⁿ`, given its Jacobian.
+pub fn newton_system(
+    f: impl Fn(&Vector<f64>) -> Vector<f64>,
+    jacobian: impl Fn(&Vector<f64>) -> Matrix<f64>,
+    x0: &Vector<f64>,
+    options: &Options,
+) -> Result<Root<Vector<f64>>> {
+    let mut x = x0.clone();
+
+    for iteration in 1..=options.max_iterations {
+        let fx = f(&x);
+        if fx.dot(&fx).sqrt() <= options.f_tolerance {
+            return Ok(Root { x, iterations: iteration - 1 });
+        }
+
+        let step = jacobian(&x)
+            .solve(&(&fx * -1.0))
+            .ok_or(Error::SingularJacobian)?;
+        x = &x + &step;
+
+        if small(step.dot(&step).sqrt(), x.dot(&x).sqrt(), options.x_tolerance) {
+            return Ok(Root { x, iterations: iteration });
+        }
+    }
+
+    Err(Error::NoConvergence { iterations: options.max_iterations })
+}
@@ -0,0 +1,411 @@
+//! Vectorised slice kernels for `f32` and `f64`.
+//!
+//! On x86_64 the AVX2+FMA versions are picked at runtime when the CPU has
+//! them (or, without `std`, when the target enables those features).
+//! Everywhere else a portable loop over independent lanes is used, which
+//! the compiler lowers to whatever SIMD the target has (SSE2 at least on
+//! x86_64). AVX-512 intrinsics aren't stable at this crate's MSRV.
+//!
+//! Reductions accumulate in several lanes, so results can differ from a
+//! sequential fold in the last few ulps. `scalar` holds the plain
+//! sequential versions the kernels are checked against.
+//!
+//! `Vector` and `Matrix` arithmetic uses these automatically when the
+//! element type is `f32` or `f64`.
+
+use alloc::vec::Vec;
+use core::any::TypeId;
+
+
+pub trait Kernels: Copy + Default + 'static {
+    fn dot(a: &[Self], b: &[Self]) -> Self;
+    fn sum(a: &[Self]) -> Self;
+    /// `y += alpha * x`
+    fn axpy(alpha: Self, x: &[Self], y: &mut [Self]);
+    fn add_into(a: &[Self], b: &[Self], out: &mut [Self]);
+    fn sub_into(a: &[Self], b: &[Self], out: &mut [Self]);
+    fn mul_into(a: &[Self], b: &[Self], out: &mut [Self]);
+}
+
+/// Sequential reference implementations, for any numeric type.
+pub mod scalar {
+    use core::ops::{Add, Mul, Sub};
+
+    pub fn dot<T: Copy + Default + Add<Output = T> + Mul<Output = T>>(a: &[T], b: &[T]) -> T {
+        a.iter().zip(b).fold(T::default(), |sum, (&x, &y)| sum + x * y)
+    }
+
+    pub fn sum<T: Copy + Default + Add<Output = T>>(a: &[T]) -> T {
+        a.iter().fold(T::default(), |sum, &x| sum + x)
+    }
+
+    pub fn axpy<T: Copy + Add<Output = T> + Mul<Output = T>>(alpha: T, x: &[T], y: &mut [T]) {
+        for (y, &x) in y.iter_mut().zip(x) {
+            *y = *y + alpha * x;
+        }
+    }
+
+    pub fn add_into<T: Copy + Add<Output = T>>(a: &[T], b: &[T], out: &mut [T]) {
+        for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+            *o = x + y;
+        }
+    }
+
+    pub fn sub_into<T: Copy + Sub<Output = T>>(a: &[T], b: &[T], out: &mut [T]) {
+        for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+            *o = x - y;
+        }
+    }
+
+    pub fn mul_into<T: Copy + Mul<Output = T>>(a: &[T], b: &[T], out: &mut [T]) {
+        for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+            *o = x * y;
+        }
+    }
+}
+
+/// Portable kernels: independent accumulators per lane so the loops vectorise.
+pub mod portable {
+    use core::ops::{Add, Mul, Sub};
+
+    pub const LANES: usize = 8;
+
+    pub fn dot<T: Copy + Default + Add<Output = T> + Mul<Output = T>>(a: &[T], b: &[T]) -> T {
+        let mut acc = [T::default(); LANES];
+        let (a_chunks, b_chunks) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+        let tail = super::scalar::dot(a_chunks.remainder(), b_chunks.remainder());
+
+        for (x, y) in a_chunks.zip(b_chunks) {
+            for lane in 0..LANES {
+                acc[lane] = acc[lane] + x[lane] * y[lane];
+            }
+        }
+
+        super::scalar::sum(&acc) + tail
+    }
+
+    pub fn sum<T: Copy + Default + Add<Output = T>>(a: &[T]) -> T {
+        let mut acc = [T::default(); LANES];
+        let chunks = a.chunks_exact(LANES);
+        let tail = super::scalar::sum(chunks.remainder());
+
+        for x in chunks {
+            for lane in 0..LANES {
+                acc[lane] = acc[lane] + x[lane];
+            }
+        }
+
+        super::scalar::sum(&acc) + tail
+    }
+
+    pub fn axpy<T: Copy + Add<Output = T> + Mul<Output = T>>(alpha: T, x: &[T], y: &mut [T]) {
+        let n = x.len().min(y.len());
+        let (x, y) = (&x[..n], &mut y[..n]);
+        for (y, x) in y.chunks_mut(LANES).zip(x.chunks(LANES)) {
+            for (y, &x) in y.iter_mut().zip(x) {
+                *y = *y + alpha * x;
+            }
+        }
+    }
+
+    pub fn zip_with<T: Copy>(a: &[T], b: &[T], out: &mut [T], op: impl Fn(T, T) -> T) {
+        let n = a.len().min(b.len()).min(out.len());
+        for ((o, x), y) in out[..n].chunks_mut(LANES).zip(a[..n].chunks(LANES)).zip(b[..n].chunks(LANES)) {
+            for ((o, &x), &y) in o.iter_mut().zip(x).zip(y) {
+                *o = op(x, y);
+            }
+        }
+    }
+
+    pub fn add_into<T: Copy + Add<Output = T>>(a: &[T], b: &[T], out: &mut [T]) {
+        zip_with(a, b, out, |x, y| x + y)
+    }
+
+    pub fn sub_into<T: Copy + Sub<Output = T>>(a: &[T], b: &[T], out: &mut [T]) {
+        zip_with(a, b, out, |x, y| x - y)
+    }
+
+    pub fn mul_into<T: Copy + Mul<Output = T>>(a: &[T], b: &[T], out: &mut [T]) {
+        zip_with(a, b, out, |x, y| x * y)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+macro_rules! avx2_kernels {
+    (
+        $module:ident, $t:ty, $lanes:expr, $vec:ty,
+        $setzero:ident, $set1:ident, $load:ident, $store:ident,
+        $add:ident, $sub:ident, $mul:ident, $fmadd:ident $(,)?
+    ) => {
+        mod $module {
+            use core::arch::x86_64::*;
+
+            const LANES: usize = $lanes;
+
+            #[target_feature(enable = "avx2,fma")]
+            unsafe fn hsum(v: $vec) -> $t {
+                let mut lanes = [0 as $t; LANES];
+                $store(lanes.as_mut_ptr(), v);
+                super::scalar::sum(&lanes)
+            }
+
+            #[target_feature(enable = "avx2,fma")]
+            pub unsafe fn dot(a: &[$t], b: &[$t]) -> $t {
+                let n = a.len().min(b.len());
+                let split = n - n % (2 * LANES);
+                let (mut acc0, mut acc1) = ($setzero(), $setzero());
+
+                let mut i = 0;
+                while i < split {
+                    acc0 = $fmadd($load(a.as_ptr().add(i)), $load(b.as_ptr().add(i)), acc0);
+                    acc1 = $fmadd($load(a.as_ptr().add(i + LANES)), $load(b.as_ptr().add(i + LANES)), acc1);
+                    i += 2 * LANES;
+                }
+
+                hsum($add(acc0, acc1)) + super::scalar::dot(&a[split..n], &b[split..n])
+            }
+
+            #[target_feature(enable = "avx2,fma")]
+            pub unsafe fn sum(a: &[$t]) -> $t {
+                let split = a.len() - a.len() % (2 * LANES);
+                let (mut acc0, mut acc1) = ($setzero(), $setzero());
+
+                let mut i = 0;
+                while i < split {
+                    acc0 = $add($load(a.as_ptr().add(i)), acc0);
+                    acc1 = $add($load(a.as_ptr().add(i + LANES)), acc1);
+                    i += 2 * LANES;
+                }
+
+                hsum($add(acc0, acc1)) + super::scalar::sum(&a[split..])
+            }
+
+            #[target_feature(enable = "avx2,fma")]
+            pub unsafe fn axpy(alpha: $t, x: &[$t], y: &mut [$t]) {
+                let n = x.len().min(y.len());
+                let split = n - n % LANES;
+                let alpha_v = $set1(alpha);
+
+                let mut i = 0;
+                while i < split {
+                    let yp = y.as_mut_ptr().add(i);
+                    $store(yp, $fmadd(alpha_v, $load(x.as_ptr().add(i)), $load(yp)));
+                    i += LANES;
+                }
+
+                super::scalar::axpy(alpha, &x[split..n], &mut y[split..n]);
+            }
+
+            #[target_feature(enable = "avx2,fma")]
+            pub unsafe fn add_into(a: &[$t], b: &[$t], out: &mut [$t]) {
+                let n = a.len().min(b.len()).min(out.len());
+                let split = n - n % LANES;
+
+                let mut i = 0;
+                while i < split {
+                    $store(out.as_mut_ptr().add(i), $add($load(a.as_ptr().add(i)), $load(b.as_ptr().add(i))));
+                    i += LANES;
+                }
+
+                super::scalar::add_into(&a[split..n], &b[split..n], &mut out[split..n]);
+            }
+
+            #[target_feature(enable = "avx2,fma")]
+            pub unsafe fn sub_into(a: &[$t], b: &[$t], out: &mut [$t]) {
+                let n = a.len().min(b.len()).min(out.len());
+                let split = n - n % LANES;
+
+                let mut i = 0;
+                while i < split {
+                    $store(out.as_mut_ptr().add(i), $sub($load(a.as_ptr().add(i)), $load(b.as_ptr().add(i))));
+                    i += LANES;
+                }
+
+                super::scalar::sub_into(&a[split..n], &b[split..n], &mut out[split..n]);
+            }
+
+            #[target_feature(enable = "avx2,fma")]
+            pub unsafe fn mul_into(a: &[$t], b: &[$t], out: &mut [$t]) {
+                let n = a.len().min(b.len()).min(out.len());
+                let split = n - n % LANES;
+
+                let mut i = 0;
+                while i < split {
+                    $store(out.as_mut_ptr().add(i), $mul($load(a.as_ptr().add(i)), $load(b.as_ptr().add(i))));
+                    i += LANES;
+                }
+
+                super::scalar::mul_into(&a[split..n], &b[split..n], &mut out[split..n]);
+            }
+        }
+    };
+}
+
+#[cfg(target_arch = "x86_64")]
+avx2_kernels!(
+    avx2_f64, f64, 4, __m256d,
+    _mm256_setzero_pd, _mm256_set1_pd, _mm256_loadu_pd, _mm256_storeu_pd,
+    _mm256_add_pd, _mm256_sub_pd, _mm256_mul_pd, _mm256_fmadd_pd,
+);
+
+#[cfg(target_arch = "x86_64")]
+avx2_kernels!(
+    avx2_f32, f32, 8, __m256,
+    _mm256_setzero_ps, _mm256_set1_ps, _mm256_loadu_ps, _mm256_storeu_ps,
+    _mm256_add_ps, _mm256_sub_ps, _mm256_mul_ps, _mm256_fmadd_ps,
+);
+
+#[cfg(all(target_arch = "x86_64", feature = "std"))]
+fn has_avx2() -> bool {
+    std::is_x86_feature_detected!("avx2") && std::is_x86_feature_detected!("fma")
+}
+
+// no runtime detection without `std`, so go by what the target was compiled for
+#[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+fn has_avx2() -> bool {
+    cfg!(all(target_feature = "avx2", target_feature = "fma"))
+}
+
+macro_rules! impl_kernels {
+    ($t:ty, $avx2:ident) => {
+        impl Kernels for $t {
+            fn dot(a: &[Self], b: &[Self]) -> Self {
+                #[cfg(target_arch = "x86_64")]
+                if has_avx2() {
+                    // SAFETY: the required CPU features were just detected
+                    return unsafe { $avx2::dot(a, b) };
+                }
+                portable::dot(a, b)
+            }
+
+            fn sum(a: &[Self]) -> Self {
+                #[cfg(target_arch = "x86_64")]
+                if has_avx2() {
+                    // SAFETY: the required CPU features were just detected
+                    return unsafe { $avx2::sum(a) };
+                }
+                portable::sum(a)
+            }
+
+            fn axpy(alpha: Self, x: &[Self], y: &mut [Self]) {
+                #[cfg(target_arch = "x86_64")]
+                if has_avx2() {
+                    // SAFETY: the required CPU features were just detected
+                    return unsafe { $avx2::axpy(alpha, x, y) };
+                }
+                portable::axpy(alpha, x, y)
+            }
+
+            fn add_into(a: &[Self], b: &[Self], out: &mut [Self]) {
+                #[cfg(target_arch = "x86_64")]
+                if has_avx2() {
+                    // SAFETY: the required CPU features were just detected
+                    return unsafe { $avx2::add_into(a, b, out) };
+                }
+                portable::add_into(a, b, out)
+            }
+
+            fn sub_into(a: &[Self], b: &[Self], out: &mut [Self]) {
+                #[cfg(target_arch = "x86_64")]
+                if has_avx2() {
+                    // SAFETY: the required CPU features were just detected
+                    return unsafe { $avx2::sub_into(a, b, out) };
+                }
+                portable::sub_into(a, b, out)
+            }
+
+            fn mul_into(a: &[Self], b: &[Self], out: &mut [Self]) {
+                #[cfg(target_arch = "x86_64")]
+                if has_avx2() {
+                    // SAFETY: the required CPU features were just detected
+                    return unsafe { $avx2::mul_into(a, b, out) };
+                }
+                portable::mul_into(a, b, out)
+            }
+        }
+    };
+}
+
+impl_kernels!(f64, avx2_f64);
+impl_kernels!(f32, avx2_f32);
+
+
+// Generic code reaches the kernels through these: each one runs the kernel
+// when `T` is `f32` or `f64` and returns `None` otherwise. Short slices are
+// left to the caller's plain loop, where dispatch would cost more than it saves.
+
+const MIN_LEN: usize = 16;
+
+fn cast<T: 'static, U: 'static>(s: &[T]) -> Option<&[U]> {
+    // SAFETY: T and U are the same type
+    (TypeId::of::<T>() == TypeId::of::<U>()).then(|| unsafe { &*(s as *const [T] as *const [U]) })
+}
+
+fn cast_mut<T: 'static, U: 'static>(s: &mut [T]) -> Option<&mut [U]> {
+    // SAFETY: T and U are the same type
+    (TypeId::of::<T>() == TypeId::of::<U>()).then(|| unsafe { &mut *(s as *mut [T] as *mut [U]) })
+}
+
+fn cast_value<T: Copy + 'static, U: Copy + 'static>(x: T) -> Option<U> {
+    cast::<T, U>(core::slice::from_ref(&x)).map(|x| x[0])
+}
+
+pub(crate) fn dot<T: Copy + 'static>(a: &[T], b: &[T]) -> Option<T> {
+    let n = a.len().min(b.len());
+    if n < MIN_LEN {
+        return None;
+    }
+    let (a, b) = (&a[..n], &b[..n]);
+    fn run<K: Kernels, T: Copy + 'static>(a: &[T], b: &[T]) -> Option<T> {
+        cast_value(K::dot(cast::<T, K>(a)?, cast::<T, K>(b)?))
+    }
+    run::<f64, T>(a, b).or_else(|| run::<f32, T>(a, b))
+}
+
+pub(crate) fn sum<T: Copy + 'static>(a: &[T]) -> Option<T> {
+    if a.len() < MIN_LEN {
+        return None;
+    }
+    fn run<K: Kernels, T: Copy + 'static>(a: &[T]) -> Option<T> {
+        cast_value(K::sum(cast::<T, K>(a)?))
+    }
+    run::<f64, T>(a).or_else(|| run::<f32, T>(a))
+}
+
+pub(crate) fn axpy<T: Copy + 'static>(alpha: T, x: &[T], y: &mut [T]) -> Option<()> {
+    if x.len() < MIN_LEN {
+        return None;
+    }
+    fn run<K: Kernels, T: Copy + 'static>(alpha: T, x: &[T], y: &mut [T]) -> Option<()> {
+        K::axpy(cast_value(alpha)?, cast::<T, K>(x)?, cast_mut::<T, K>(y)?);
+        Some(())
+    }
+    run::<f64, T>(alpha, x, y).or_else(|| run::<f32, T>(alpha, x, y))
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Op {
+    Add,
+    Sub,
+    Mul,
+}
+
+pub(crate) fn zip_with<T: Copy + 'static>(a: &[T], b: &[T], op: Op) -> Option<Vec<T>> {
+    let n = a.len().min(b.len());
+    if n < MIN_LEN {
+        return None;
+    }
+    let (a, b) = (&a[..n], &b[..n]);
+    fn run<K: Kernels, T: Copy + 'static>(a: &[T], b: &[T], op: Op) -> Option<Vec<T>> {
+        let (x, y) = (cast::<T, K>(a)?, cast::<T, K>(b)?);
+        let mut out = a.to_vec();
+        let dst = cast_mut::<T, K>(&mut out)?;
+        match op {
+            Op::Add => K::add_into(x, y, dst),
+            Op::Sub => K::sub_into(x, y, dst),
+            Op::Mul => K::mul_into(x, y, dst),
+        }
+        Some(out)
+    }
+    run::<f64, T>(a, b, op).or_else(|| run::<f32, T>(a, b, op))
+}
@@ -0,0 +1,86 @@
+//! Buffers that can hold a `Matrix`'s elements, row-major.
+//!
+//! Implement `Storage` for a buffer type, e.g. one carved out of an arena or
+//! a received packet, to wrap it in a `Matrix` without copying. Arithmetic
+//! on any storage returns an owned, `Vec`-backed matrix.
+
+use alloc::{boxed::Box, rc::Rc, vec::Vec};
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+
+pub trait Storage<T> {
+    fn as_slice(&self) -> &[T];
+}
+
+/// Storage whose elements can be modified in place.
+pub trait StorageMut<T>: Storage<T> {
+    fn as_mut_slice(&mut self) -> &mut [T];
+}
+
+impl<T> Storage<T> for Vec<T> {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> StorageMut<T> for Vec<T> {
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T> Storage<T> for &[T] {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> Storage<T> for &mut [T] {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> StorageMut<T> for &mut [T] {
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize> Storage<T> for [T; N] {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, const N: usize> StorageMut<T> for [T; N] {
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T> Storage<T> for Box<[T]> {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> StorageMut<T> for Box<[T]> {
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T> Storage<T> for Rc<[T]> {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T> Storage<T> for Arc<[T]> {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
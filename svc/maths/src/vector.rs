@@ -1,12 +1,19 @@
-use std::ops::{
+use alloc::vec::Vec;
+
+use core::ops::{
     Add,
     Sub,
     Mul,
     Index,
     IndexMut,
+    Deref,
+    DerefMut,
 };
 
+use core::slice::SliceIndex;
+
 use crate::Matrix;
+use crate::simd::{self, Op};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Vector<T> {
@@ -21,11 +28,15 @@ impl<T> Vector<T> {
 
 impl<T> Add for &Vector<T>
 where
-    T: Add<Output = T> + Copy,
+    T: Add<Output = T> + Copy + 'static,
 {
     type Output = Vector<T>;
 
     fn add(self, other: Self) -> Self::Output {
+        if let Some(elements) = simd::zip_with(&self.elements, &other.elements, Op::Add) {
+            return Vector::new(elements);
+        }
+
         let elements = self
             .elements
             .iter()
@@ -38,7 +49,7 @@ where
 
 impl<T> Sub for &Vector<T>
 where
-    T: Sub<Output = T> + Copy,
+    T: Sub<Output = T> + Copy + 'static,
 {
     type Output = Vector<T>;
 
@@ -49,6 +60,10 @@ where
             "Vectors must be the same length to subtract."
         );
 
+        if let Some(elements) = simd::zip_with(&self.elements, &other.elements, Op::Sub) {
+            return Vector::new(elements);
+        }
+
         let elements = self
             .elements
             .iter()
@@ -79,7 +94,7 @@ where
 
 impl<T> Vector<T>
 where
-    T: Copy + Mul<Output = T>,
+    T: Copy + Mul<Output = T> + 'static,
 {
     pub fn element_wise_mul(&self, other: &Self) -> Self {
         assert_eq!(
@@ -88,6 +103,10 @@ where
             "Vectors must be the same length for element-wise multiplication."
         );
 
+        if let Some(elements) = simd::zip_with(&self.elements, &other.elements, Op::Mul) {
+            return Vector::new(elements);
+        }
+
         let elements = self
             .elements
             .iter()
@@ -101,14 +120,28 @@ where
 
 impl<T> Vector<T>
 where
-    T: Mul<Output = T> + Add<Output = T> + Copy + Default,
+    T: Mul<Output = T> + Add<Output = T> + Copy + Default + 'static,
 {
     pub fn dot(&self, other: &Self) -> T {
-        self.elements
-            .iter()
-            .zip(&other.elements)
-            .map(|(&a, &b)| a * b)
-            .fold(T::default(), |sum, val| sum + val)
+        simd::dot(&self.elements, &other.elements)
+            .unwrap_or_else(|| simd::scalar::dot(&self.elements, &other.elements))
+    }
+
+    pub fn sum(&self) -> T {
+        simd::sum(&self.elements).unwrap_or_else(|| simd::scalar::sum(&self.elements))
+    }
+
+    /// `self += alpha * x`, in place.
+    pub fn axpy(&mut self, alpha: T, x: &Self) {
+        assert_eq!(
+            self.elements.len(),
+            x.elements.len(),
+            "Vectors must be the same length for axpy."
+        );
+
+        if simd::axpy(alpha, &x.elements, &mut self.elements).is_none() {
+            simd::scalar::axpy(alpha, &x.elements, &mut self.elements);
+        }
     }
 }
 
@@ -118,7 +151,7 @@ where
 {
     pub fn from_fn(size: usize, mut f: impl FnMut(usize) -> T) -> Self {
         let elements = (0..size)
-            .map(|i| f(i))
+            .map(&mut f)
             .collect();
 
         Vector::new(elements)
@@ -145,28 +178,111 @@ where
             })
             .collect();
 
-        Matrix {
-            rows: self.elements.len(),
-            cols: other.elements.len(),
-            elements,
-        }
+        Matrix::new(self.elements.len(), other.elements.len(), elements)
 
     }
 
 }
 
-impl<T> Index<usize> for Vector<T>  {
-    type Output = T;
+impl<T, I: SliceIndex<[T]>> Index<I> for Vector<T> {
+    type Output = I::Output;
 
-    fn index(&self, index: usize) -> &T {
+    fn index(&self, index: I) -> &I::Output {
         &self.elements[index]
     }
 }
 
-impl<T> IndexMut<usize> for Vector<T> {
-    fn index_mut(&mut self, index: usize) -> &mut T {
+impl<T, I: SliceIndex<[T]>> IndexMut<I> for Vector<T> {
+    fn index_mut(&mut self, index: I) -> &mut I::Output {
         &mut self.elements[index]
     }
 }
 
+impl<T> Deref for Vector<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.elements
+    }
+}
+
+impl<T> DerefMut for Vector<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.elements
+    }
+}
+
+impl<T> AsRef<[T]> for Vector<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.elements
+    }
+}
 
+impl<T> AsMut<[T]> for Vector<T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        &mut self.elements
+    }
+}
+
+impl<T> From<Vec<T>> for Vector<T> {
+    fn from(elements: Vec<T>) -> Self {
+        Vector::new(elements)
+    }
+}
+
+impl<T> From<Vector<T>> for Vec<T> {
+    fn from(vector: Vector<T>) -> Self {
+        vector.elements
+    }
+}
+
+impl<T: Clone> From<&[T]> for Vector<T> {
+    fn from(elements: &[T]) -> Self {
+        Vector::new(elements.to_vec())
+    }
+}
+
+impl<T> FromIterator<T> for Vector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Vector::new(iter.into_iter().collect())
+    }
+}
+
+impl<T> Extend<T> for Vector<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.elements.extend(iter);
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for Vector<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.elements.extend(iter);
+    }
+}
+
+impl<T> IntoIterator for Vector<T> {
+    type Item = T;
+    type IntoIter = alloc::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Vector<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Vector<T> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.iter_mut()
+    }
+}
@@ -0,0 +1,54 @@
+use maths::{assert_matrix_eq, matrix, vector, ApproxEq, Matrix};
+
+
+#[test]
+fn scalar_comparisons() {
+
+    assert!(0.1f64.abs_diff_eq(&0.10001, 1e-4));
+    assert!(!0.1f64.abs_diff_eq(&0.10001, 1e-6));
+
+    assert!(1e9f64.relative_eq(&(1e9 + 1.0), f64::EPSILON, 1e-8));
+    assert!(!f64::NAN.relative_eq(&f64::NAN, 1.0, 1.0));
+    assert!(!f64::NAN.ulps_eq(&f64::NAN, 1.0, u32::MAX));
+    assert!(!f32::NAN.ulps_eq(&f32::NAN, 0.0, 4));
+
+    let next = f32::from_bits(1.0f32.to_bits() + 2);
+    assert!(1.0f32.ulps_eq(&next, 0.0, 2));
+    assert!(!1.0f32.ulps_eq(&next, 0.0, 1));
+
+}
+
+#[test]
+fn matrix_and_vector_comparisons() {
+
+    let a = matrix![[0.1 + 0.2, 1.0], [2.0, 3.0]];
+    let b = matrix![[0.3, 1.0], [2.0, 3.0]];
+
+    assert!(a.relative_eq(&b, f64::EPSILON, f64::EPSILON));
+    assert!(a.ulps_eq(&b, 0.0, 1));
+    assert!(!a.abs_diff_eq(&b, 0.0));
+    assert!(!a.abs_diff_eq(&Matrix::new(1, 4, a.elements.clone()), 1.0));
+
+    let v = vector![1.0f32, 2.0];
+    assert!(v.abs_diff_eq(&vector![1.001, 1.999], 0.01));
+    assert!(!v.abs_diff_eq(&vector![1.0], 0.01));
+
+    assert_matrix_eq!(a, b);
+    assert_matrix_eq!(a, b, max_ulps = 1);
+    assert_matrix_eq!(v, vector![1.001, 1.999], epsilon = 0.01);
+
+}
+
+#[test]
+fn assert_matrix_eq_reports_positions() {
+
+    let result = std::panic::catch_unwind(|| {
+        let m = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+        assert_matrix_eq!(m, matrix![[1.0, 2.5], [3.0, 4.0]], max_relative = 1e-3);
+    });
+
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("1 of 4 elements differ"), "{}", message);
+    assert!(message.contains("(0, 1): left = 2.0, right = 2.5"), "{}", message);
+
+}
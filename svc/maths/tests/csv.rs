@@ -0,0 +1,130 @@
+#![cfg(feature = "std")]
+
+use maths::{
+    io::{csv::{CsvOptions, Missing}, mtx::{self, Format}, Error},
+    Matrix,
+};
+
+
+#[test]
+fn csv_with_header_and_delimiter() {
+
+    let input = "x;\"y; label\"\n1;2.5\n3;-4e1\n";
+    let options = CsvOptions {
+        delimiter: b';',
+        has_header: true,
+        ..CsvOptions::default()
+    };
+
+    let (m, header) = Matrix::from_csv_reader(input.as_bytes(), &options).unwrap();
+
+    assert_eq!(header, Some(vec!["x".to_string(), "y; label".to_string()]));
+    assert_eq!((m.rows, m.cols), (2, 2));
+    assert_eq!(m.elements, vec![1.0, 2.5, 3.0, -40.0]);
+
+    let mut out = Vec::new();
+    m.to_csv_writer(&mut out, &options, Some(&["x", "y; label"])).unwrap();
+
+    assert_eq!(String::from_utf8(out).unwrap(), "x;\"y; label\"\n1;2.5\n3;-40\n");
+
+}
+
+#[test]
+fn csv_missing_value_policies() {
+
+    let input = "1,2\n,4\n5,NA\n";
+
+    let err = Matrix::from_csv_reader(input.as_bytes(), &CsvOptions::default()).unwrap_err();
+    assert!(matches!(err, Error::Parse { line: 2, .. }));
+
+    let fill = CsvOptions { missing: Missing::Fill(0.0), ..CsvOptions::default() };
+    let (m, _) = Matrix::from_csv_reader(input.as_bytes(), &fill).unwrap();
+    assert_eq!(m.elements, vec![1.0, 2.0, 0.0, 4.0, 5.0, 0.0]);
+
+    let skip = CsvOptions { missing: Missing::SkipRow, ..CsvOptions::default() };
+    let (m, _) = Matrix::from_csv_reader(input.as_bytes(), &skip).unwrap();
+    assert_eq!((m.rows, m.cols), (1, 2));
+
+    let ragged = Matrix::from_csv_reader("1,2\n3\n".as_bytes(), &CsvOptions::default());
+    assert!(matches!(ragged, Err(Error::Parse { line: 2, .. })));
+
+}
+
+#[test]
+fn csv_rejects_bad_headers() {
+
+    let m = Matrix::new(1, 2, vec![1.0, 2.0]);
+    let mut out = Vec::new();
+
+    let short = m.to_csv_writer(&mut out, &CsvOptions::default(), Some(&["x"]));
+    assert!(matches!(short, Err(Error::ShapeMismatch { ref expected, ref found }) if expected == "2 header fields" && found == &[1]));
+
+    // the reader couldn't split a field spanning lines back out
+    let multi_line = m.to_csv_writer(&mut out, &CsvOptions::default(), Some(&["x", "y\nz"]));
+    assert!(matches!(multi_line, Err(Error::Unsupported(_))));
+
+    // nor blank records, which it skips
+    let empty_rows = Matrix::<f64>::new(2, 0, vec![]).to_csv_writer(&mut out, &CsvOptions::default(), None);
+    assert!(matches!(empty_rows, Err(Error::Unsupported(_))));
+
+}
+
+#[test]
+fn matrix_market_symmetric_coordinate() {
+
+    let input = "\
+%%MatrixMarket matrix coordinate real symmetric
+% lower triangle only
+3 3 4
+1 1 2.0
+2 1 -1.0
+3 2 -1.0
+3 3 2.0
+";
+
+    let m = mtx::read_matrix(input.as_bytes()).unwrap();
+
+    assert_eq!(m.elements, vec![
+        2.0, -1.0, 0.0,
+        -1.0, 0.0, -1.0,
+        0.0, -1.0, 2.0,
+    ]);
+
+}
+
+#[test]
+fn matrix_market_round_trip() {
+
+    let m = Matrix::new(2, 3, vec![1.0, 0.0, 3.5, 0.0, -2.0, 0.0]);
+
+    for format in [Format::Array, Format::Coordinate] {
+        let mut out = Vec::new();
+        mtx::write_matrix(&mut out, &m, format).unwrap();
+
+        let read = mtx::read_matrix(out.as_slice()).unwrap();
+        assert_eq!((read.rows, read.cols), (2, 3));
+        assert_eq!(read.elements, m.elements);
+    }
+
+}
+
+#[test]
+fn matrix_market_rejects_bad_sizes() {
+
+    let rectangular = "%%MatrixMarket matrix coordinate real symmetric\n2 3 1\n2 1 1.0\n";
+    assert!(matches!(mtx::read_matrix(rectangular.as_bytes()), Err(Error::Parse { line: 2, .. })));
+
+    let skew = "%%MatrixMarket matrix array real skew-symmetric\n3 2\n1.0\n";
+    assert!(matches!(mtx::read_matrix(skew.as_bytes()), Err(Error::Parse { line: 2, .. })));
+
+    let overflowing = format!("%%MatrixMarket matrix coordinate real general\n{} 2 0\n", usize::MAX);
+    assert!(matches!(mtx::read_matrix(overflowing.as_bytes()), Err(Error::Parse { line: 2, .. })));
+
+    let huge = "%%MatrixMarket matrix coordinate real general\n1000000000000 1000000 0\n";
+    assert!(matches!(mtx::read_matrix(huge.as_bytes()), Err(Error::Parse { line: 2, .. })));
+
+    // fits in memory only on paper, past `MAX_ELEMENTS`
+    let dense = "%%MatrixMarket matrix coordinate real general\n100000 100000 0\n";
+    assert!(matches!(mtx::read_matrix(dense.as_bytes()), Err(Error::Parse { line: 2, .. })));
+
+}
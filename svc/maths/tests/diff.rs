@@ -0,0 +1,59 @@
+use maths::diff::{self, Step};
+use maths::{assert_matrix_eq, matrix, vector, Vector};
+
+
+fn f(x: &Vector<f64>) -> f64 {
+    x[0] * x[0] * x[1] + x[1].sin() + 3.0 * x[0]
+}
+
+#[test]
+fn test_gradient() {
+
+    let x: Vector<f64> = vector![1.5, -0.5];
+    let expected = vector![2.0 * x[0] * x[1] + 3.0, x[0] * x[0] + x[1].cos()];
+
+    assert_matrix_eq!(diff::gradient(f, &x), expected, epsilon = 1e-9);
+
+}
+
+#[test]
+fn test_jacobian() {
+
+    let g = |x: &Vector<f64>| vector![x[0] * x[1], x[0].exp(), x[1] * x[1]];
+    let x = vector![0.5, 2.0];
+
+    let jacobian = diff::jacobian(g, &x);
+
+    assert_eq!((jacobian.rows, jacobian.cols), (3, 2));
+    assert_matrix_eq!(jacobian, matrix![[2.0, 0.5], [0.5f64.exp(), 0.0], [0.0, 4.0]], epsilon = 1e-9);
+
+}
+
+#[test]
+fn test_hessian() {
+
+    let x: Vector<f64> = vector![1.5, -0.5];
+    let expected = matrix![[2.0 * x[1], 2.0 * x[0]], [2.0 * x[0], -x[1].sin()]];
+
+    let hessian = diff::hessian(f, &x);
+
+    assert_matrix_eq!(hessian, expected, epsilon = 1e-6);
+    assert_eq!(hessian[(0, 1)], hessian[(1, 0)]);
+
+}
+
+#[test]
+fn test_configurable_step() {
+
+    let x: Vector<f64> = vector![1.5, -0.5];
+    let expected = diff::gradient(f, &x);
+
+    // a coarse step visibly loses accuracy on the nonlinear coordinate
+    let coarse = Step::Absolute(0.5).gradient(f, &x);
+    let fine = Step::Absolute(1e-6).gradient(f, &x);
+
+    assert!((coarse[1] - expected[1]).abs() > 1e-3);
+    assert_matrix_eq!(fine, expected, epsilon = 1e-8);
+    assert_matrix_eq!(Step::Relative(1e-4).hessian(f, &x), diff::hessian(f, &x), epsilon = 1e-6);
+
+}
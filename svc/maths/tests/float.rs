@@ -0,0 +1,19 @@
+use maths::{ApproxEq, Float};
+
+
+// runs against `std` by default and against `libm` with
+// `--no-default-features --features libm`
+#[test]
+fn float_functions() {
+
+    let tolerance = 1e-15;
+
+    assert!(Float::sqrt(2.0f64).abs_diff_eq(&std::f64::consts::SQRT_2, tolerance));
+    assert!(Float::exp(1.0f64).abs_diff_eq(&std::f64::consts::E, tolerance));
+    assert!(Float::ln(std::f64::consts::E).abs_diff_eq(&1.0, tolerance));
+    assert!(Float::powi(1.5f64, 3).abs_diff_eq(&3.375, tolerance));
+    assert!(Float::hypot(3.0f64, 4.0).abs_diff_eq(&5.0, tolerance));
+    assert_eq!(Float::abs(-2.5f32), 2.5);
+    assert_eq!(Float::floor(-1.5f32), -2.0);
+
+}
@@ -0,0 +1,50 @@
+use maths::{matrix, vector, Matrix, Vector};
+
+
+#[test]
+fn display_aligns_columns() {
+
+    let m = matrix![[1.0, -22.5], [300.0, 4.0]];
+
+    assert_eq!(m.to_string(), "[[  1, -22.5],\n [300,     4]]");
+    assert_eq!(format!("{:.2}", m), "[[  1.00, -22.50],\n [300.00,   4.00]]");
+    assert_eq!(format!("{:.1}", vector![0.25, 10.0]), "[ 0.2, 10.0]");
+
+}
+
+#[test]
+fn display_truncates_large_matrices() {
+
+    let m = Matrix::from_fn(100, 100, |i, j| i * 100 + j);
+    let text = m.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert_eq!(lines.len(), 7);
+    assert_eq!(lines[0], "[[   0,    1,    2, ...,   97,   98,   99],");
+    assert_eq!(lines[3], " ...,");
+    assert_eq!(lines[6], " [9900, 9901, 9902, ..., 9997, 9998, 9999]]");
+
+    let v = Vector::from_fn(2000, |i| i);
+    assert_eq!(v.to_string(), "[   0,    1,    2, ..., 1997, 1998, 1999]");
+
+}
+
+#[test]
+fn parse_literals() {
+
+    let m: Matrix<i32> = "[[1, 2], [3, 4],]".parse().unwrap();
+    assert_eq!((m.rows, m.cols), (2, 2));
+    assert_eq!(m.elements, vec![1, 2, 3, 4]);
+
+    let v: Vector<f64> = " [1.5, -2e3] ".parse().unwrap();
+    assert_eq!(v, vector![1.5, -2000.0]);
+
+    assert!("[[1, 2], [3]]".parse::<Matrix<i32>>().is_err());
+    assert!("[1, x]".parse::<Vector<i32>>().is_err());
+    assert!("[1, 2] 3".parse::<Vector<i32>>().is_err());
+
+    // display output parses back
+    let round_trip: Matrix<f64> = matrix![[0.5, 1.0], [2.0, -3.0]].to_string().parse().unwrap();
+    assert_eq!(round_trip.elements, vec![0.5, 1.0, 2.0, -3.0]);
+
+}
@@ -0,0 +1,91 @@
+use maths::interp::{self, Bilinear, CubicSpline, Error, Linear};
+use maths::{assert_matrix_eq, matrix, vector};
+
+
+#[test]
+fn test_linear_interpolation_clamps_outside() {
+
+    let linear = Linear::new(&[0.0, 1.0, 3.0], &[0.0, 10.0, 30.0]).unwrap();
+
+    assert_eq!(linear.eval(0.5), 5.0);
+    assert_eq!(linear.eval(2.0), 20.0);
+    assert_eq!(linear.eval(1.0), 10.0);
+    assert_eq!(linear.eval_many(&[-1.0, 4.0]), vector![0.0, 30.0]);
+
+    let single = Linear::new(&[1.0], &[7.0]).unwrap();
+    assert_eq!(single.eval_many(&[0.0, 1.0, 2.0, f64::NAN]), vector![7.0, 7.0, 7.0, 7.0]);
+
+}
+
+#[test]
+fn test_invalid_samples() {
+
+    assert_eq!(Linear::new(&[0.0, 1.0], &[1.0]).unwrap_err(), Error::LengthMismatch { xs: 2, ys: 1 });
+    assert_eq!(Linear::new(&[0.0, 2.0, 1.0], &[0.0; 3]).unwrap_err(), Error::NotIncreasing { index: 2 });
+    assert_eq!(CubicSpline::natural(&[0.0], &[0.0]).unwrap_err(), Error::TooFewPoints { needed: 2, found: 1 });
+    assert_eq!(Linear::new(&[0.0, f64::NAN, 2.0], &[0.0; 3]).unwrap_err(), Error::NotIncreasing { index: 1 });
+    assert_eq!(CubicSpline::natural(&[f64::NAN, 1.0], &[0.0; 2]).unwrap_err(), Error::NotIncreasing { index: 1 });
+
+}
+
+#[test]
+fn test_natural_spline() {
+
+    let xs: Vec<f64> = (0..=10).map(|i| i as f64 * 0.5).collect();
+    let ys: Vec<f64> = xs.iter().map(|x| x.sin()).collect();
+    let spline = CubicSpline::natural(&xs, &ys).unwrap();
+
+    for (x, y) in xs.iter().zip(&ys) {
+        assert!((spline.eval(*x) - y).abs() < 1e-14);
+    }
+    assert!((spline.eval(1.25) - 1.25f64.sin()).abs() < 1e-3);
+
+    // a natural spline through collinear points is the line itself
+    let line = CubicSpline::natural(&[0.0, 1.0, 3.0, 4.0], &[1.0, 3.0, 7.0, 9.0]).unwrap();
+    assert!((line.eval(2.0) - 5.0).abs() < 1e-12);
+
+}
+
+#[test]
+fn test_clamped_spline_reproduces_cubic() {
+
+    let cubic = |x: f64| x * x * x - 2.0 * x;
+    let xs = [-1.0, 0.0, 0.5, 2.0];
+    let ys: Vec<f64> = xs.iter().map(|&x| cubic(x)).collect();
+
+    let spline = CubicSpline::clamped(&xs, &ys, 1.0, 10.0).unwrap();
+
+    for x in [-0.7, 0.2, 1.3] {
+        assert!((spline.eval(x) - cubic(x)).abs() < 1e-12);
+        assert!((spline.derivative(x) - (3.0 * x * x - 2.0)).abs() < 1e-12);
+    }
+
+}
+
+#[test]
+fn test_polyfit_recovers_coefficients() {
+
+    let xs: Vec<f64> = (0..20).map(|i| i as f64 * 0.1).collect();
+    let ys: Vec<f64> = xs.iter().map(|x| 0.5 - 2.0 * x + 3.0 * x * x).collect();
+
+    let coefficients = interp::polyfit(&xs, &ys, 2).unwrap();
+
+    assert_matrix_eq!(coefficients, vector![0.5, -2.0, 3.0], epsilon = 1e-10);
+    assert!((interp::polyval(&coefficients, 1.5) - 4.25).abs() < 1e-10);
+    assert_eq!(interp::polyfit(&xs[..2], &ys[..2], 2).unwrap_err(), Error::TooFewPoints { needed: 3, found: 2 });
+    assert_eq!(interp::polyfit(&[1.0, 1.0, 1.0], &[0.0, 1.0, 2.0], 1).unwrap_err(), Error::RankDeficient);
+
+}
+
+#[test]
+fn test_bilinear() {
+
+    // f(x, y) = x + 2y is reproduced exactly
+    let grid = matrix![[0.0, 2.0, 4.0], [1.0, 3.0, 5.0]];
+    let bilinear = Bilinear::new(&[0.0, 1.0], &[0.0, 1.0, 2.0], &grid).unwrap();
+
+    assert!((bilinear.eval(0.25, 1.5) - 3.25).abs() < 1e-12);
+    assert_eq!(bilinear.eval(5.0, -1.0), 1.0);
+    assert!(matches!(Bilinear::new(&[0.0, 1.0], &[0.0, 1.0], &grid), Err(Error::GridMismatch { .. })));
+
+}
@@ -0,0 +1,169 @@
+use maths::{assert_matrix_eq, matrix, vector, Matrix};
+#[cfg(feature = "num")]
+use maths::Vector;
+#[cfg(feature = "num")]
+use num::{BigInt, BigRational};
+
+
+#[cfg(feature = "num")]
+fn q(numer: i64, denom: i64) -> BigRational {
+    BigRational::new(BigInt::from(numer), BigInt::from(denom))
+}
+
+#[cfg(feature = "num")]
+fn rational(rows: usize, cols: usize, elements: &[i64]) -> Matrix<BigRational> {
+    Matrix::new(rows, cols, elements.iter().map(|&x| q(x, 1)).collect())
+}
+
+
+#[test]
+fn test_lu_reconstructs_permuted_matrix() {
+
+    let a = matrix![[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [4.0, 3.0, 2.0]];
+    let lu = a.lu();
+
+    let pa = Matrix::from_fn(3, 3, |i, j| a[(lu.permutation()[i], j)]);
+
+    assert!(!lu.is_singular());
+    assert_matrix_eq!(&lu.l() * &lu.u(), pa, epsilon = 1e-12);
+
+}
+
+#[test]
+fn test_float_solve_determinant_and_inverse() {
+
+    let a = matrix![[2.0, 1.0], [1.0, 3.0]];
+    let x = a.solve(&vector![3.0, 5.0]).unwrap();
+
+    assert_matrix_eq!(x, vector![0.8, 1.4], epsilon = 1e-12);
+    assert!((a.determinant() - 5.0_f64).abs() < 1e-12);
+    assert_matrix_eq!(&a * &a.inverse().unwrap(), Matrix::identity(2), epsilon = 1e-12);
+
+}
+
+#[test]
+fn test_float_rank_ignores_rounding_noise() {
+
+    let a = matrix![[0.1, 0.2, 0.3], [0.4, 0.5, 0.6], [0.7, 0.8, 0.9]];
+
+    assert_eq!(a.rank(), 2);
+    assert!(a.inverse().is_none());
+
+}
+
+#[test]
+#[cfg(feature = "num")]
+fn test_rational_determinant_is_exact() {
+
+    // Hilbert matrices are notoriously ill-conditioned in floating point
+    let hilbert = Matrix::from_fn(5, 5, |i, j| q(1, (i + j + 1) as i64));
+
+    assert_eq!(hilbert.determinant(), q(1, 266_716_800_000));
+
+}
+
+#[test]
+#[cfg(feature = "num")]
+fn test_rational_inverse_round_trips() {
+
+    let hilbert = Matrix::from_fn(4, 4, |i, j| q(1, (i + j + 1) as i64));
+    let inverse = hilbert.inverse().unwrap();
+
+    assert_eq!(inverse[(0, 0)], q(16, 1));
+    assert_eq!(inverse[(3, 3)], q(2800, 1));
+    assert_eq!(&hilbert * &inverse, Matrix::identity(4));
+
+}
+
+#[test]
+#[cfg(feature = "num")]
+fn test_rational_solve() {
+
+    let a = rational(3, 3, &[2, 1, -1, -3, -1, 2, -2, 1, 2]);
+    let b = Vector::new(vec![q(8, 1), q(-11, 1), q(-3, 1)]);
+
+    assert_eq!(a.solve(&b).unwrap(), Vector::new(vec![q(2, 1), q(3, 1), q(-1, 1)]));
+
+}
+
+#[test]
+#[cfg(feature = "num")]
+fn test_rational_singular() {
+
+    let a = rational(3, 3, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    assert_eq!(a.rank(), 2);
+    assert_eq!(a.determinant(), q(0, 1));
+    assert!(a.lu().is_singular());
+    assert!(a.inverse().is_none());
+
+}
+
+#[test]
+#[cfg(feature = "num")]
+fn test_rref_pivot_columns() {
+
+    let a = rational(3, 4, &[1, 2, 1, 1, 2, 4, 0, 6, 1, 2, 2, -1]);
+    let (r, pivots) = a.rref();
+
+    assert_eq!(pivots, vec![0, 2]);
+    assert_eq!(r, rational(3, 4, &[1, 2, 0, 3, 0, 0, 1, -2, 0, 0, 0, 0]));
+
+}
+
+#[test]
+fn test_float_rref_matches_exact() {
+
+    let a = matrix![[1.0, 2.0, 1.0, 1.0], [2.0, 4.0, 0.0, 6.0], [1.0, 2.0, 2.0, -1.0]];
+    let (r, pivots) = a.rref();
+
+    assert_eq!(pivots, vec![0, 2]);
+    assert_matrix_eq!(r, matrix![[1.0, 2.0, 0.0, 3.0], [0.0, 0.0, 1.0, -2.0], [0.0, 0.0, 0.0, 0.0]], epsilon = 1e-12);
+
+}
+
+#[test]
+fn test_qr_factors() {
+
+    let a = matrix![[12.0, -51.0, 4.0], [6.0, 167.0, -68.0], [-4.0, 24.0, -41.0], [1.0, 2.0, 3.0]];
+    let qr = a.qr();
+    let (q, r) = (qr.q(), qr.r());
+
+    assert_matrix_eq!(&q.transpose() * &q, Matrix::identity(4), epsilon = 1e-12);
+    assert_matrix_eq!(&q * &r, a, epsilon = 1e-10);
+    assert!((0..3).all(|j| (j + 1..4).all(|i| r[(i, j)] == 0.0)));
+
+}
+
+#[test]
+fn test_least_squares_line() {
+
+    let a = matrix![[1.0, 0.0], [1.0, 1.0], [1.0, 2.0], [1.0, 3.0]];
+    let b = vector![1.5, 2.5, 5.5, 6.5];
+
+    let x = a.least_squares(&b).unwrap();
+
+    assert_matrix_eq!(x, vector![1.3, 1.8], epsilon = 1e-12);
+    assert!(matrix![[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]].least_squares(&vector![1.0, 2.0, 3.0]).is_none());
+
+}
+
+#[test]
+#[cfg(feature = "num")]
+fn test_eigenvalues() {
+
+    let symmetric = matrix![[2.0, 1.0, 0.0], [1.0, 2.0, 1.0], [0.0, 1.0, 2.0]];
+    let mut values: Vec<f64> = symmetric.eigenvalues().unwrap().iter().map(|z| z.re).collect();
+    values.sort_by(f64::total_cmp);
+
+    let sqrt2 = 2f64.sqrt();
+    for (value, expected) in values.iter().zip([2.0 - sqrt2, 2.0, 2.0 + sqrt2]) {
+        assert!((value - expected).abs() < 1e-12, "{:?}", values);
+    }
+
+    // a rotation by 90° has eigenvalues ±i
+    let rotation = matrix![[0.0, -1.0], [1.0, 0.0]];
+    let values = rotation.eigenvalues().unwrap();
+    assert!(values.iter().all(|z| z.re.abs() < 1e-15 && (z.im.abs() - 1.0).abs() < 1e-15));
+
+}
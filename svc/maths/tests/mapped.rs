@@ -0,0 +1,85 @@
+#![cfg(feature = "std")]
+
+use maths::{
+    io::{mapped::{self, MappedMatrix}, Error},
+    Matrix,
+    Vector,
+};
+use std::path::PathBuf;
+
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("maths-mapped-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn rows_and_batches() {
+
+    let path = temp_path("rows");
+    let m = Matrix::from_fn(5, 3, |row, col| (row * 3 + col) as f64);
+    mapped::save_matrix(&path, &m).unwrap();
+
+    let mapped = MappedMatrix::<f64>::open(&path).unwrap();
+    assert_eq!((mapped.rows, mapped.cols), (5, 3));
+    assert_eq!(mapped.row(1), &[3.0, 4.0, 5.0]);
+    assert_eq!(mapped.row_range(3..5), &[9.0, 10.0, 11.0, 12.0, 13.0, 14.0]);
+    assert_eq!(mapped.to_matrix(), m);
+
+    let batches: Vec<_> = mapped.batches(2).collect();
+    assert_eq!(batches.iter().map(|b| b.rows).collect::<Vec<_>>(), vec![2, 2, 1]);
+    assert_eq!(batches[2].elements, &[12.0, 13.0, 14.0]);
+    assert_eq!(mapped.batch(1..3), mapped.view(1..3));
+
+    // batches work with the in-memory arithmetic
+    let v = Vector::new(vec![1.0, 0.0, -1.0]);
+    assert_eq!(&batches[0] * &v, Vector::new(vec![-2.0, -2.0]));
+    assert_eq!(&mapped * &v, &m * &v);
+
+    std::fs::remove_file(&path).unwrap();
+
+}
+
+#[test]
+fn integer_elements() {
+
+    let path = temp_path("integers");
+    let m = Matrix::new(2, 2, vec![1i32, -2, 3, -4]);
+    mapped::save_matrix(&path, &m).unwrap();
+
+    let mapped = MappedMatrix::<i32>::open(&path).unwrap();
+    assert_eq!(mapped.as_slice(), &[1, -2, 3, -4]);
+    assert_eq!(&mapped * &Vector::new(vec![1, 1]), Vector::new(vec![-1, -1]));
+
+    std::fs::remove_file(&path).unwrap();
+
+}
+
+#[test]
+fn rejects_bad_files() {
+
+    let path = temp_path("bad");
+    mapped::save_matrix(&path, &Matrix::new(2, 2, vec![1.0f32, 2.0, 3.0, 4.0])).unwrap();
+
+    assert!(matches!(
+        MappedMatrix::<f64>::open(&path),
+        Err(Error::DtypeMismatch { .. }),
+    ));
+
+    // truncated data
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes.pop();
+    std::fs::write(&path, &bytes).unwrap();
+    assert!(matches!(
+        MappedMatrix::<f32>::open(&path),
+        Err(Error::ShapeMismatch { .. }),
+    ));
+
+    std::fs::write(&path, b"not a matrix").unwrap();
+    assert!(matches!(
+        MappedMatrix::<f32>::open(&path),
+        Err(Error::Format(_)),
+    ));
+
+    std::fs::remove_file(&path).unwrap();
+
+}
@@ -37,3 +37,41 @@ fn test_matrix_vector_multiplication() {
     assert_eq!(result.elements, vec![50.0, 122.0]);
 
 }
+
+#[test]
+fn test_row_and_column_iterators() {
+
+    let mut m = Matrix::new(2, 3, vec![1, 2, 3, 4, 5, 6]);
+
+    let rows: Vec<&[i32]> = m.rows().collect();
+    assert_eq!(rows, vec![&[1, 2, 3][..], &[4, 5, 6][..]]);
+
+    let cols: Vec<Vec<i32>> = m.cols().map(|col| col.copied().collect()).collect();
+    assert_eq!(cols, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+
+    for row in m.rows_mut() {
+        row.reverse();
+    }
+    m.iter_mut().for_each(|x| *x *= 10);
+
+    assert_eq!(m.iter().copied().collect::<Vec<_>>(), vec![30, 20, 10, 60, 50, 40]);
+    assert_eq!((&m).into_iter().max(), Some(&60));
+
+    // zero-column matrices still have their rows
+    let mut empty: Matrix<i32> = Matrix::new(3, 0, vec![]);
+    assert_eq!(empty.rows().count(), 3);
+    assert_eq!(empty.rows_mut().count(), 3);
+
+}
+
+#[test]
+fn test_non_square_matrix_multiplication() {
+
+    let a = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = Matrix::new(3, 1, vec![1.0, 0.0, -1.0]);
+    let product = &a * &b;
+
+    assert_eq!((product.rows, product.cols), (2, 1));
+    assert_eq!(product.elements, vec![-2.0, -2.0]);
+
+}
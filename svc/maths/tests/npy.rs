@@ -0,0 +1,181 @@
+#![cfg(feature = "std")]
+
+use maths::{
+    io::{npy::{self, NpzReader, NpzWriter, Order}, Error},
+    Matrix,
+    Vector,
+};
+use std::io::Cursor;
+
+
+fn npy_bytes(descr: &str, fortran_order: bool, shape: &str, data: &[u8]) -> Vec<u8> {
+    let header = format!(
+        "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}\n",
+        descr,
+        if fortran_order { "True" } else { "False" },
+        shape,
+    );
+
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+#[test]
+fn matrix_round_trip() {
+
+    let m = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    for order in [Order::C, Order::Fortran] {
+        let mut bytes = Vec::new();
+        npy::write_matrix(&mut bytes, &m, order).unwrap();
+
+        // the header is padded so the data starts on a 64-byte boundary
+        assert_eq!(bytes.len(), 128 + 6 * 8);
+
+        let read: Matrix<f64> = npy::read_matrix(bytes.as_slice()).unwrap();
+        assert_eq!((read.rows, read.cols), (2, 3));
+        assert_eq!(read.elements, m.elements);
+    }
+
+}
+
+#[test]
+fn read_fortran_order_big_endian() {
+
+    // [[1, 2, 3], [4, 5, 6]] stored column by column
+    let data: Vec<u8> = [1i32, 4, 2, 5, 3, 6]
+        .iter()
+        .flat_map(|x| x.to_be_bytes())
+        .collect();
+    let bytes = npy_bytes(">i4", true, "(2, 3)", &data);
+
+    let m: Matrix<i32> = npy::read_matrix(bytes.as_slice()).unwrap();
+
+    assert_eq!((m.rows, m.cols), (2, 3));
+    assert_eq!(m.elements, vec![1, 2, 3, 4, 5, 6]);
+
+}
+
+#[test]
+fn vector_round_trip() {
+
+    let v = Vector::new(vec![1.5f32, -2.0, 0.25]);
+
+    let mut bytes = Vec::new();
+    npy::write_vector(&mut bytes, &v).unwrap();
+
+    assert_eq!(npy::read_vector::<f32, _>(bytes.as_slice()).unwrap(), v);
+
+}
+
+#[test]
+fn rejects_mismatched_dtype_and_shape() {
+
+    let bytes = npy_bytes("<i8", false, "(3,)", &[0u8; 24]);
+
+    assert!(matches!(
+        npy::read_vector::<f64, _>(bytes.as_slice()),
+        Err(Error::DtypeMismatch { .. }),
+    ));
+    assert!(matches!(
+        npy::read_matrix::<i64, _>(bytes.as_slice()),
+        Err(Error::ShapeMismatch { .. }),
+    ));
+
+}
+
+#[test]
+fn rejects_truncated_and_oversized_data() {
+
+    // a shape far larger than the data must not be allocated up front
+    let bytes = npy_bytes("<f8", false, "(1000000000000, 1000)", &[0u8; 16]);
+    assert!(matches!(npy::read_matrix::<f64, _>(bytes.as_slice()), Err(Error::Format(_))));
+
+    let bytes = npy_bytes("<f8", false, "(3,)", &[0u8; 16]);
+    assert!(matches!(npy::read_vector::<f64, _>(bytes.as_slice()), Err(Error::Format(_))));
+
+}
+
+#[test]
+fn rejects_oversized_header() {
+
+    // a version 2.0 header length of 4 GiB with nothing behind it
+    let mut bytes = b"\x93NUMPY\x02\x00".to_vec();
+    bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(npy::read_vector::<f64, _>(bytes.as_slice()), Err(Error::Format(_))));
+
+}
+
+#[test]
+fn npz_round_trip() {
+
+    let weights = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+    let labels = Vector::new(vec![0i64, 1, 1]);
+
+    let mut npz = NpzWriter::new(Cursor::new(Vec::new()));
+    npz.add_matrix("weights", &weights).unwrap();
+    npz.add_vector("labels", &labels).unwrap();
+    let archive = npz.finish().unwrap().into_inner();
+
+    let mut npz = NpzReader::new(Cursor::new(archive)).unwrap();
+
+    assert_eq!(npz.names().collect::<Vec<_>>(), vec!["weights", "labels"]);
+    assert_eq!(npz.matrix::<f64>("weights").unwrap().elements, weights.elements);
+    assert_eq!(npz.vector::<i64>("labels").unwrap(), labels);
+    assert!(matches!(npz.vector::<i64>("biases"), Err(Error::MissingArray(_))));
+
+}
+
+#[test]
+fn npz_rejects_entries_past_the_end() {
+
+    let name = b"huge.npy";
+
+    // an empty stored entry whose zip64 extra field claims 2^62 bytes
+    let mut archive = Vec::new();
+    archive.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+    archive.extend_from_slice(&[0u8; 22]);
+    archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    archive.extend_from_slice(&0u16.to_le_bytes());
+    archive.extend_from_slice(name);
+
+    let cd_offset = archive.len() as u32;
+    archive.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+    archive.extend_from_slice(&[0u8; 16]); // versions, flags, method, time, date and crc
+    archive.extend_from_slice(&u32::MAX.to_le_bytes());
+    archive.extend_from_slice(&u32::MAX.to_le_bytes());
+    archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    archive.extend_from_slice(&20u16.to_le_bytes());
+    archive.extend_from_slice(&[0u8; 10]); // comment, disk, internal and external attributes
+    archive.extend_from_slice(&0u32.to_le_bytes());
+    archive.extend_from_slice(name);
+    archive.extend_from_slice(&1u16.to_le_bytes());
+    archive.extend_from_slice(&16u16.to_le_bytes());
+    archive.extend_from_slice(&(1u64 << 62).to_le_bytes());
+    archive.extend_from_slice(&(1u64 << 62).to_le_bytes());
+
+    let cd_size = archive.len() as u32 - cd_offset;
+    archive.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    archive.extend_from_slice(&[0u8; 4]);
+    archive.extend_from_slice(&1u16.to_le_bytes());
+    archive.extend_from_slice(&1u16.to_le_bytes());
+    archive.extend_from_slice(&cd_size.to_le_bytes());
+    archive.extend_from_slice(&cd_offset.to_le_bytes());
+    archive.extend_from_slice(&0u16.to_le_bytes());
+
+    let mut npz = NpzReader::new(Cursor::new(archive)).unwrap();
+    assert!(matches!(npz.vector::<f64>("huge"), Err(Error::Format(_))));
+
+}
+
+#[test]
+fn npz_rejects_long_names() {
+
+    let mut npz = NpzWriter::new(Cursor::new(Vec::new()));
+    let name = "x".repeat(u16::MAX as usize);
+    assert!(matches!(npz.add_vector(&name, &Vector::new(vec![1.0])), Err(Error::Unsupported(_))));
+
+}
@@ -0,0 +1,72 @@
+use maths::ode::{self, Error, Options};
+use maths::{vector, Vector};
+
+
+// simple harmonic oscillator, y = (position, velocity)
+fn oscillator(_t: f64, y: &Vector<f64>) -> Vector<f64> {
+    vector![y[1], -y[0]]
+}
+
+#[test]
+fn test_rk4_exponential_decay() {
+
+    let trajectory = ode::rk4(|_, y: &Vector<f64>| y * -1.0, 0.0, &vector![1.0], 1.0, 100);
+
+    assert_eq!(trajectory.t.len(), 101);
+    assert_eq!(*trajectory.t.last().unwrap(), 1.0);
+    assert!((trajectory.last()[0] - (-1.0f64).exp()).abs() < 1e-10);
+
+}
+
+#[test]
+fn test_dormand_prince_oscillator() {
+
+    let t1 = 2.0 * std::f64::consts::PI;
+    let options = Options {
+        relative_tolerance: 1e-10,
+        absolute_tolerance: 1e-12,
+        ..Options::default()
+    };
+    let trajectory = ode::dormand_prince(oscillator, 0.0, &vector![1.0, 0.0], t1, &options).unwrap();
+
+    assert_eq!(*trajectory.t.last().unwrap(), t1);
+    assert!((trajectory.last()[0] - 1.0).abs() < 1e-8);
+    assert!(trajectory.last()[1].abs() < 1e-8);
+
+}
+
+#[test]
+fn test_dormand_prince_adapts_step_size() {
+
+    let loose = ode::dormand_prince(oscillator, 0.0, &vector![1.0, 0.0], 10.0, &Options::default()).unwrap();
+    let tight = ode::dormand_prince(
+        oscillator,
+        0.0,
+        &vector![1.0, 0.0],
+        10.0,
+        &Options { relative_tolerance: 1e-10, ..Options::default() },
+    ).unwrap();
+
+    assert!(tight.t.len() > loose.t.len());
+    assert!(tight.t.windows(2).all(|w| w[1] > w[0]));
+
+}
+
+#[test]
+fn test_dormand_prince_integrates_backwards() {
+
+    let trajectory = ode::dormand_prince(|_, y: &Vector<f64>| y.clone(), 1.0, &vector![1.0], 0.0, &Options::default()).unwrap();
+
+    assert!((trajectory.last()[0] - (-1.0f64).exp()).abs() < 1e-6);
+
+}
+
+#[test]
+fn test_dormand_prince_reports_step_limit() {
+
+    let options = Options { max_steps: 3, ..Options::default() };
+    let result = ode::dormand_prince(oscillator, 0.0, &vector![1.0, 0.0], 100.0, &options);
+
+    assert!(matches!(result, Err(Error::MaxSteps { .. })));
+
+}
@@ -0,0 +1,138 @@
+use maths::optimize::{self, Options, Status, WithGradient};
+use maths::{vector, Matrix, Vector};
+
+
+fn rosenbrock(x: &Vector<f64>) -> f64 {
+    (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2)
+}
+
+fn rosenbrock_gradient(x: &Vector<f64>) -> Vector<f64> {
+    vector![
+        -2.0 * (1.0 - x[0]) - 400.0 * x[0] * (x[1] - x[0] * x[0]),
+        200.0 * (x[1] - x[0] * x[0]),
+    ]
+}
+
+fn assert_near(x: &Vector<f64>, expected: &[f64], tolerance: f64) {
+    for (a, b) in x.iter().zip(expected) {
+        assert!((a - b).abs() < tolerance, "{:?} is not within {} of {:?}", x, tolerance, expected);
+    }
+}
+
+
+#[test]
+fn test_gradient_descent_on_quadratic() {
+
+    let quadratic = |x: &Vector<f64>| (x[0] - 3.0).powi(2) + 10.0 * (x[1] + 1.0).powi(2);
+    let result = optimize::gradient_descent(&quadratic, &vector![0.0, 0.0], &Options::default());
+
+    assert!(result.converged(), "{:?}", result);
+    assert_near(&result.x, &[3.0, -1.0], 1e-5);
+
+}
+
+#[test]
+fn test_conjugate_gradient_on_rosenbrock() {
+
+    let objective = WithGradient { value: rosenbrock, gradient: rosenbrock_gradient };
+    let options = Options { max_iterations: 10_000, ..Options::default() };
+    let result = optimize::conjugate_gradient(&objective, &vector![-1.2, 1.0], &options);
+
+    assert!(result.converged(), "{:?}", result);
+    assert_near(&result.x, &[1.0, 1.0], 1e-4);
+
+}
+
+#[test]
+fn test_lbfgs_on_rosenbrock() {
+
+    let objective = WithGradient { value: rosenbrock, gradient: rosenbrock_gradient };
+    let result = optimize::lbfgs(&objective, &vector![-1.2, 1.0], 5, &Options::default());
+
+    assert_eq!(result.status, Status::Converged);
+    assert!(result.iterations < 100, "took {} iterations", result.iterations);
+    assert!(result.gradient_norm < 1e-6);
+    assert_near(&result.x, &[1.0, 1.0], 1e-6);
+
+}
+
+#[test]
+fn test_lbfgs_with_numeric_gradient() {
+
+    let result = optimize::lbfgs(&rosenbrock, &vector![-1.2, 1.0], 5, &Options::default());
+
+    assert!(result.converged(), "{:?}", result);
+    assert_near(&result.x, &[1.0, 1.0], 1e-5);
+
+}
+
+#[test]
+fn test_nelder_mead_on_rosenbrock() {
+
+    let result = optimize::nelder_mead(&rosenbrock, &vector![-1.2, 1.0], &Options::default());
+
+    assert!(result.converged(), "{:?}", result);
+    assert!(result.gradient_norm < 1e-3);
+    assert_near(&result.x, &[1.0, 1.0], 1e-4);
+
+}
+
+#[test]
+fn test_max_iterations_is_reported() {
+
+    let options = Options { max_iterations: 3, ..Options::default() };
+    let result = optimize::gradient_descent(&rosenbrock, &vector![-1.2, 1.0], &options);
+
+    assert_eq!(result.status, Status::MaxIterations);
+    assert_eq!(result.iterations, 3);
+
+}
+
+#[test]
+fn test_levenberg_marquardt_fits_exponential() {
+
+    let ts: Vec<f64> = (0..20).map(|i| i as f64 * 0.25).collect();
+    let ys: Vec<f64> = ts.iter().map(|t| 2.5 * (-1.3 * t).exp() + 0.5).collect();
+
+    let residuals = |p: &Vector<f64>| -> Vector<f64> {
+        ts.iter().zip(&ys).map(|(t, y)| p[0] * (p[1] * t).exp() + p[2] - y).collect()
+    };
+    let result = optimize::levenberg_marquardt(&residuals, &vector![1.0, -0.5, 0.0], &Options::default());
+
+    assert!(result.converged(), "{:?}", result);
+    assert!(result.value < 1e-16);
+    assert_near(&result.x, &[2.5, -1.3, 0.5], 1e-6);
+
+}
+
+#[test]
+fn test_levenberg_marquardt_with_analytic_jacobian() {
+
+    struct Circle(Vec<(f64, f64)>);
+
+    impl optimize::LeastSquares for Circle {
+        fn residuals(&self, p: &Vector<f64>) -> Vector<f64> {
+            self.0.iter().map(|(x, y)| ((x - p[0]).powi(2) + (y - p[1]).powi(2)).sqrt() - p[2]).collect()
+        }
+
+        fn jacobian(&self, p: &Vector<f64>) -> Option<Matrix<f64>> {
+            let rows = self.0.iter().flat_map(|(x, y)| {
+                let d = ((x - p[0]).powi(2) + (y - p[1]).powi(2)).sqrt();
+                [(p[0] - x) / d, (p[1] - y) / d, -1.0]
+            });
+            Some(Matrix::new(self.0.len(), 3, rows.collect()))
+        }
+    }
+
+    let points = (0..8)
+        .map(|i| {
+            let angle = i as f64 * 0.7;
+            (1.0 + 2.0 * angle.cos(), -2.0 + 2.0 * angle.sin())
+        })
+        .collect();
+    let result = optimize::levenberg_marquardt(&Circle(points), &vector![0.0, 0.0, 1.0], &Options::default());
+
+    assert!(result.converged(), "{:?}", result);
+    assert_near(&result.x, &[1.0, -2.0, 2.0], 1e-8);
+
+}
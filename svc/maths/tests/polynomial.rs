@@ -0,0 +1,118 @@
+use maths::Polynomial;
+#[cfg(feature = "num")]
+use maths::vector;
+#[cfg(feature = "num")]
+use num::{BigInt, BigRational};
+
+
+fn poly(coefficients: &[f64]) -> Polynomial<f64> {
+    Polynomial::new(coefficients.to_vec().into())
+}
+
+#[test]
+fn test_arithmetic_and_trimming() {
+
+    let (p, q) = (poly(&[1.0, 2.0, 3.0]), poly(&[4.0, 5.0]));
+
+    assert_eq!(&p + &q, poly(&[5.0, 7.0, 3.0]));
+    assert_eq!(&p - &p, Polynomial::zero());
+    assert_eq!((&p - &p).degree(), None);
+    assert_eq!(&p * &q, poly(&[4.0, 13.0, 22.0, 15.0]));
+    assert_eq!(-&q, poly(&[-4.0, -5.0]));
+    assert_eq!(poly(&[1.0, 0.0, 0.0]).degree(), Some(0));
+
+}
+
+#[test]
+fn test_long_division() {
+
+    // x³ - 2x² - 4 = (x - 3)(x² + x + 3) + 5
+    let (dividend, divisor) = (poly(&[-4.0, 0.0, -2.0, 1.0]), poly(&[-3.0, 1.0]));
+    let (quotient, remainder) = dividend.div_rem(&divisor);
+
+    assert_eq!(quotient, poly(&[3.0, 1.0, 1.0]));
+    assert_eq!(remainder, poly(&[5.0]));
+    assert_eq!(&(&quotient * &divisor) + &remainder, dividend);
+    assert_eq!(&divisor / &dividend, Polynomial::zero());
+    assert_eq!(&divisor % &dividend, divisor);
+
+}
+
+#[test]
+fn test_calculus_and_evaluation() {
+
+    let p = poly(&[1.0, -3.0, 0.0, 2.0]);
+
+    assert_eq!(p.eval(&2.0), 11.0);
+    assert_eq!(p.derivative(), poly(&[-3.0, 0.0, 6.0]));
+    assert_eq!(p.derivative().integral(), poly(&[0.0, -3.0, 0.0, 2.0]));
+    assert_eq!(poly(&[7.0]).derivative(), Polynomial::zero());
+
+}
+
+#[test]
+#[cfg(feature = "num")]
+fn test_exact_rational_division() {
+
+    let q = |n: i64, d: i64| BigRational::new(BigInt::from(n), BigInt::from(d));
+    let p = Polynomial::new(vec![q(1, 1), q(0, 1), q(3, 1)].into());
+    let divisor = Polynomial::new(vec![q(1, 2), q(2, 1)].into());
+
+    let (quotient, remainder) = p.div_rem(&divisor);
+
+    assert_eq!(quotient.coefficients(), &vec![q(-3, 8), q(3, 2)].into());
+    assert_eq!(remainder.coefficients(), &vec![q(19, 16)].into());
+
+}
+
+#[test]
+#[cfg(feature = "num")]
+fn test_roots_round_trip() {
+
+    let p = Polynomial::from_roots(&[1.0, -2.0, 3.5]);
+    assert_eq!(p.coefficients(), &vector![7.0, -5.5, -2.5, 1.0]);
+
+    let mut roots: Vec<f64> = p.roots().unwrap().iter().map(|r| {
+        assert!(r.im.abs() < 1e-12);
+        r.re
+    }).collect();
+    roots.sort_by(f64::total_cmp);
+
+    for (root, expected) in roots.iter().zip([-2.0, 1.0, 3.5]) {
+        assert!((root - expected).abs() < 1e-10, "{:?}", roots);
+    }
+
+}
+
+#[test]
+#[cfg(feature = "num")]
+fn test_complex_roots() {
+
+    // x⁴ + 1 has roots at the odd eighth roots of unity
+    let roots = poly(&[1.0, 0.0, 0.0, 0.0, 1.0]).roots().unwrap();
+    let half_sqrt2 = 0.5f64.sqrt();
+
+    assert_eq!(roots.len(), 4);
+    for root in &roots {
+        assert!((root.re.abs() - half_sqrt2).abs() < 1e-12, "{:?}", roots);
+        assert!((root.im.abs() - half_sqrt2).abs() < 1e-12, "{:?}", roots);
+    }
+    assert!(poly(&[3.0]).roots().is_none());
+
+}
+
+#[test]
+#[cfg(feature = "num")]
+fn test_high_degree_roots() {
+
+    let expected: Vec<f64> = (1..=10).map(|i| i as f64).collect();
+    let p = Polynomial::from_roots(&expected);
+
+    let mut roots: Vec<f64> = p.roots().unwrap().iter().map(|r| r.re).collect();
+    roots.sort_by(f64::total_cmp);
+
+    for (root, expected) in roots.iter().zip(&expected) {
+        assert!((root - expected).abs() < 1e-6, "{:?}", roots);
+    }
+
+}
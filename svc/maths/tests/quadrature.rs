@@ -0,0 +1,47 @@
+use maths::quadrature::{self, Options};
+use std::f64::consts::PI;
+
+
+#[test]
+fn test_fixed_rules_converge_at_their_order() {
+
+    let exact = 2.0;
+
+    let trapezoid = quadrature::trapezoid(f64::sin, 0.0, PI, 100);
+    let simpson = quadrature::simpson(f64::sin, 0.0, PI, 100);
+
+    assert!((trapezoid - exact).abs() < 2e-4);
+    assert!((simpson - exact).abs() < 2e-8);
+
+}
+
+#[test]
+fn test_simpson_is_exact_for_cubics() {
+
+    let integral = quadrature::simpson(|x| x * x * x - x, 0.0, 2.0, 3);
+
+    assert!((integral - 2.0).abs() < 1e-14);
+
+}
+
+#[test]
+fn test_gauss_kronrod_smooth() {
+
+    let integral = quadrature::gauss_kronrod(|x: f64| (-x * x).exp(), -5.0, 5.0, &Options::default());
+
+    assert!(integral.converged);
+    assert!((integral.value - PI.sqrt()).abs() < 1e-10);
+
+}
+
+#[test]
+fn test_gauss_kronrod_adapts_to_singularity() {
+
+    // integrable singularity at 0
+    let integral = quadrature::gauss_kronrod(|x: f64| 1.0 / x.sqrt(), 0.0, 1.0, &Options::default());
+
+    assert!(integral.converged, "{:?}", integral);
+    assert!((integral.value - 2.0).abs() < 1e-8);
+    assert!(integral.evaluations > 15);
+
+}
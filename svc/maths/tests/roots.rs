@@ -0,0 +1,66 @@
+use maths::roots::{self, Error, Options};
+use maths::{matrix, vector, Vector};
+
+
+#[test]
+fn test_bisection_and_brent_find_cube_root() {
+
+    let f = |x: f64| x * x * x - 2.0;
+    let expected = 2f64.cbrt();
+
+    let bisected = roots::bisection(f, 0.0, 2.0, &Options::default()).unwrap();
+    let brent = roots::brent(f, 0.0, 2.0, &Options::default()).unwrap();
+
+    assert!((bisected.x - expected).abs() < 1e-11);
+    assert!((brent.x - expected).abs() < 1e-12);
+    assert!(brent.iterations < bisected.iterations);
+
+}
+
+#[test]
+fn test_brent_on_transcendental() {
+
+    let root = roots::brent(|x: f64| x.cos() - x, 0.0, 1.0, &Options::default()).unwrap();
+
+    assert!((root.x - 0.739_085_133_215_160_6).abs() < 1e-12);
+
+}
+
+#[test]
+fn test_unbracketed_interval_is_rejected() {
+
+    let f = |x: f64| x * x + 1.0;
+
+    assert_eq!(roots::bisection(f, -1.0, 1.0, &Options::default()), Err(Error::NotBracketed));
+    assert_eq!(roots::brent(f, -1.0, 1.0, &Options::default()), Err(Error::NotBracketed));
+
+}
+
+#[test]
+fn test_newton() {
+
+    let root = roots::newton(|x| x * x - 2.0, |x| 2.0 * x, 1.0, &Options::default()).unwrap();
+
+    assert!((root.x - 2f64.sqrt()).abs() < 1e-14);
+    assert!(root.iterations <= 7);
+    assert_eq!(
+        roots::newton(|x| x * x - 2.0, |x| 2.0 * x, 0.0, &Options::default()),
+        Err(Error::ZeroDerivative { x: 0.0 }),
+    );
+
+}
+
+#[test]
+fn test_newton_system() {
+
+    // intersection of the unit circle with the line y = x
+    let f = |v: &Vector<f64>| vector![v[0] * v[0] + v[1] * v[1] - 1.0, v[0] - v[1]];
+    let jacobian = |v: &Vector<f64>| matrix![[2.0 * v[0], 2.0 * v[1]], [1.0, -1.0]];
+
+    let root = roots::newton_system(f, jacobian, &vector![1.0, 0.5], &Options::default()).unwrap();
+    let expected = 0.5f64.sqrt();
+
+    assert!((root.x[0] - expected).abs() < 1e-12);
+    assert!((root.x[1] - expected).abs() < 1e-12);
+
+}
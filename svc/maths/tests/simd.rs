@@ -0,0 +1,114 @@
+use maths::{
+    simd::{portable, scalar, Kernels},
+    ApproxEq, Vector,
+};
+
+
+// deterministic values in [-1, 1) without pulling in an rng
+fn values(len: usize, seed: u64) -> Vec<f64> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+        })
+        .collect()
+}
+
+// odd lengths exercise every remainder after the vector lanes
+const LENGTHS: [usize; 8] = [0, 1, 3, 7, 8, 17, 33, 1025];
+
+#[test]
+fn f64_kernels_match_scalar() {
+
+    for len in LENGTHS {
+        let (a, b) = (values(len, 1), values(len, 2));
+        let tolerance = 1e-12 * len.max(1) as f64;
+
+        for dot in [f64::dot(&a, &b), portable::dot(&a, &b)] {
+            assert!(dot.abs_diff_eq(&scalar::dot(&a, &b), tolerance), "dot, len {}", len);
+        }
+        for sum in [f64::sum(&a), portable::sum(&a)] {
+            assert!(sum.abs_diff_eq(&scalar::sum(&a), tolerance), "sum, len {}", len);
+        }
+
+        let mut expected = vec![0.0; len];
+        let mut out = vec![0.0; len];
+
+        scalar::add_into(&a, &b, &mut expected);
+        f64::add_into(&a, &b, &mut out);
+        assert_eq!(out, expected);
+        portable::add_into(&a, &b, &mut out);
+        assert_eq!(out, expected);
+
+        scalar::sub_into(&a, &b, &mut expected);
+        f64::sub_into(&a, &b, &mut out);
+        assert_eq!(out, expected);
+
+        scalar::mul_into(&a, &b, &mut expected);
+        f64::mul_into(&a, &b, &mut out);
+        assert_eq!(out, expected);
+        portable::mul_into(&a, &b, &mut out);
+        assert_eq!(out, expected);
+
+        let mut expected = b.clone();
+        scalar::axpy(0.5, &a, &mut expected);
+        for axpy in [f64::axpy as fn(f64, &[f64], &mut [f64]), portable::axpy] {
+            let mut y = b.clone();
+            axpy(0.5, &a, &mut y);
+            assert!(
+                y.iter().zip(&expected).all(|(y, e)| y.abs_diff_eq(e, 1e-15)),
+                "axpy, len {}",
+                len,
+            );
+        }
+    }
+
+}
+
+#[test]
+fn f32_kernels_match_scalar() {
+
+    for len in LENGTHS {
+        let a: Vec<f32> = values(len, 3).into_iter().map(|x| x as f32).collect();
+        let b: Vec<f32> = values(len, 4).into_iter().map(|x| x as f32).collect();
+        let tolerance = 1e-5 * len.max(1) as f32;
+
+        assert!(f32::dot(&a, &b).abs_diff_eq(&scalar::dot(&a, &b), tolerance), "dot, len {}", len);
+        assert!(f32::sum(&a).abs_diff_eq(&scalar::sum(&a), tolerance), "sum, len {}", len);
+
+        let mut expected = vec![0.0; len];
+        let mut out = vec![0.0; len];
+        scalar::add_into(&a, &b, &mut expected);
+        f32::add_into(&a, &b, &mut out);
+        assert_eq!(out, expected);
+    }
+
+}
+
+#[test]
+fn vector_ops_use_kernels() {
+
+    let (a, b) = (Vector::new(values(101, 5)), Vector::new(values(101, 6)));
+
+    assert!(a.dot(&b).abs_diff_eq(&scalar::dot(&a, &b), 1e-12));
+    assert!(a.sum().abs_diff_eq(&scalar::sum(&a), 1e-12));
+    assert_eq!((&a + &b).elements, a.iter().zip(&b).map(|(x, y)| x + y).collect::<Vec<_>>());
+    assert_eq!(a.element_wise_mul(&b).elements, a.iter().zip(&b).map(|(x, y)| x * y).collect::<Vec<_>>());
+
+    let mut y = b.clone();
+    y.axpy(2.0, &a);
+    assert!(y.abs_diff_eq(&(&b + &(&a * 2.0)), 1e-15));
+
+    // non-float element types keep the generic path
+    let mut v = Vector::new(vec![1, 2, 3]);
+    v.axpy(2, &Vector::new(vec![1, 1, 1]));
+    assert_eq!(v, Vector::new(vec![3, 4, 5]));
+    assert_eq!(v.sum(), 12);
+
+    // mismatched lengths pair up the common prefix, as the generic path does
+    let short = Vector::new(values(40, 7));
+    assert!(a.dot(&short).abs_diff_eq(&scalar::dot(&a[..40], &short), 1e-12));
+    assert_eq!((&a + &short).elements, a.iter().zip(&short).map(|(x, y)| x + y).collect::<Vec<_>>());
+
+}
@@ -0,0 +1,51 @@
+use maths::{Matrix, Vector};
+use std::sync::Arc;
+
+
+#[test]
+fn borrowed_and_shared_storage() {
+
+    let buffer = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+
+    let borrowed = Matrix::new(2, 3, &buffer[..]);
+    let array = Matrix::new(2, 3, buffer);
+    let shared: Matrix<f64, Arc<[f64]>> = Matrix::new(2, 3, Arc::from(&buffer[..]));
+    let owned = Matrix::new(2, 3, buffer.to_vec());
+
+    // equality compares shapes and elements, not storage
+    assert_eq!(borrowed, owned);
+    assert_eq!(array, shared);
+    assert_eq!(shared.to_owned(), owned);
+
+    assert_eq!(borrowed[(1, 2)], 6.0);
+    assert_eq!(shared.rows().nth(1), Some(&[4.0, 5.0, 6.0][..]));
+
+    // arithmetic across storages returns owned matrices
+    let sum: Matrix<f64> = &borrowed + &shared;
+    assert_eq!(sum.elements, vec![2.0, 4.0, 6.0, 8.0, 10.0, 12.0]);
+    assert_eq!((&array - &owned).elements, vec![0.0; 6]);
+    assert_eq!((&borrowed * 2.0).elements, vec![2.0, 4.0, 6.0, 8.0, 10.0, 12.0]);
+
+    let product = &borrowed * &shared.transpose();
+    assert_eq!(product, Matrix::new(2, 2, vec![14.0, 32.0, 32.0, 77.0]));
+    assert_eq!(&array * &Vector::new(vec![1.0, 0.0, -1.0]), Vector::new(vec![-2.0, -2.0]));
+
+}
+
+#[test]
+fn mutable_storage() {
+
+    let mut buffer = [0; 4];
+    {
+        let mut m = Matrix::new(2, 2, &mut buffer[..]);
+        m[(0, 1)] = 3;
+        for row in m.rows_mut() {
+            row[0] += 1;
+        }
+    }
+    assert_eq!(buffer, [1, 3, 1, 0]);
+
+    let owned = Matrix::new(2, 2, vec![1, 2, 3, 4]);
+    assert_eq!(&owned.view() * 1, owned);
+
+}
@@ -36,3 +36,30 @@ fn dot_product() {
 
 }
 
+
+#[test]
+fn iterator_ergonomics() {
+
+    let v: Vector<i32> = (1..=3).collect();
+    assert_eq!(v, Vector::from(vec![1, 2, 3]));
+
+    let doubled: Vector<i32> = v.iter().map(|x| x * 2).collect();
+    assert_eq!(doubled, Vector::new(vec![2, 4, 6]));
+
+    let mut v = v;
+    v.extend([4, 5]);
+    v.extend(&[6]);
+    for x in &mut v {
+        *x -= 1;
+    }
+
+    // slice methods come through `Deref`
+    assert_eq!(v.len(), 6);
+    assert_eq!(v.iter().sum::<i32>(), 15);
+    assert_eq!(&v[1..3], &[1, 2]);
+    assert_eq!(v.as_ref().first(), Some(&0));
+
+    let elements: Vec<i32> = v.into();
+    assert_eq!(elements, vec![0, 1, 2, 3, 4, 5]);
+
+}
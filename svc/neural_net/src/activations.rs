@@ -1,22 +1,53 @@
 
+use maths::Vector;
 
-#[derive(Clone, Copy)]
+
+/// `ReLU`, `Sigmoid` and `Tanh` act on each element independently;
+/// `Softmax` and `LogSoftmax` act on a sample's outputs as a whole, so only
+/// `activate_vector` and `backward` handle every variant.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ActivationFunction {
     ReLU,
     Sigmoid,
     Tanh,
+    /// `e^z_i / Σ_j e^z_j`, probabilities summing to 1.
+    Softmax,
+    /// `z_i - ln Σ_j e^z_j`, the logarithm of `Softmax` without its
+    /// underflow.
+    LogSoftmax,
+}
+
+// log-sum-exp with the maximum subtracted, so no exponent is positive
+pub(crate) fn log_softmax(z: &Vector<f64>) -> Vector<f64> {
+    let max = z.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let log_sum = z.iter().map(|x| (x - max).exp()).sum::<f64>().ln() + max;
+    z.map(|x| x - log_sum)
 }
 
 impl ActivationFunction {
 
+    /// Whether the activation acts on each element independently.
+    pub fn is_element_wise(&self) -> bool {
+        !matches!(self, ActivationFunction::Softmax | ActivationFunction::LogSoftmax)
+    }
+
+    /// # Panics
+    ///
+    /// For activations that aren't element-wise.
     pub fn activate(&self, x: f64) -> f64 {
         match self {
             ActivationFunction::ReLU => x.max(0.0),
             ActivationFunction::Sigmoid => 1.0 / (1.0 + (-x).exp()),
             ActivationFunction::Tanh => x.tanh(),
+            ActivationFunction::Softmax | ActivationFunction::LogSoftmax => {
+                panic!("{:?} acts on whole vectors; use activate_vector.", self)
+            },
         }
     }
 
+    /// # Panics
+    ///
+    /// For activations that aren't element-wise.
     pub fn derivative(&self, x: f64) -> f64 {
         match self {
             ActivationFunction::ReLU => {
@@ -29,8 +60,39 @@ impl ActivationFunction {
             ActivationFunction::Tanh => {
                 1.0 - x.tanh().powi(2)
             },
+            ActivationFunction::Softmax | ActivationFunction::LogSoftmax => {
+                panic!("{:?} acts on whole vectors; use backward.", self)
+            },
         }
     }
 
-}
+    /// The activation of one sample's pre-activations `z`.
+    pub fn activate_vector(&self, z: &Vector<f64>) -> Vector<f64> {
+        match self {
+            ActivationFunction::Softmax => log_softmax(z).map(|x| x.exp()),
+            ActivationFunction::LogSoftmax => log_softmax(z),
+            _ => z.map(|&x| self.activate(x)),
+        }
+    }
 
+    /// Backpropagates `output_gradient` through the activation at `z`: the
+    /// product `Jᵀ g` of the activation's Jacobian at `z` with it.
+    pub fn backward(&self, z: &Vector<f64>, output_gradient: &Vector<f64>) -> Vector<f64> {
+        match self {
+            ActivationFunction::Softmax => {
+                // J = diag(s) - s sᵀ
+                let s = self.activate_vector(z);
+                let projection = s.dot(output_gradient);
+                Vector::from_fn(z.len(), |i| s[i] * (output_gradient[i] - projection))
+            },
+            ActivationFunction::LogSoftmax => {
+                // J = I - 1 sᵀ
+                let s = ActivationFunction::Softmax.activate_vector(z);
+                let total = output_gradient.sum();
+                Vector::from_fn(z.len(), |i| output_gradient[i] - s[i] * total)
+            },
+            _ => Vector::from_fn(z.len(), |i| output_gradient[i] * self.derivative(z[i])),
+        }
+    }
+
+}
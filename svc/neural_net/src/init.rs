@@ -0,0 +1,96 @@
+
+use crate::activations::ActivationFunction;
+use maths::Matrix;
+use rand::Rng;
+use std::f64::consts::PI;
+
+
+/// How to fill a layer's `fan_out × fan_in` weight matrix. The scaled
+/// schemes keep activation variance roughly constant from layer to layer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Initializer {
+    /// Glorot: variance `2 / (fan_in + fan_out)`, suited to sigmoid and tanh.
+    XavierUniform,
+    XavierNormal,
+    /// Kaiming: variance `2 / fan_in`, suited to ReLU.
+    HeUniform,
+    HeNormal,
+    /// Variance `1 / fan_in`.
+    LeCunUniform,
+    LeCunNormal,
+    /// A random (semi-)orthogonal matrix scaled by `gain`.
+    Orthogonal {
+        gain: f64,
+    },
+    /// Drawn from `[low, high]`; `weights` panics unless `low <= high`.
+    Uniform {
+        low: f64,
+        high: f64,
+    },
+    Zeros,
+    Constant(f64),
+}
+
+impl Initializer {
+
+    /// The usual scheme for layers followed by `activation`.
+    pub fn for_activation(activation: ActivationFunction) -> Self {
+        match activation {
+            ActivationFunction::ReLU => Initializer::HeNormal,
+            ActivationFunction::Sigmoid
+            | ActivationFunction::Tanh
+            | ActivationFunction::Softmax
+            | ActivationFunction::LogSoftmax => Initializer::XavierUniform,
+        }
+    }
+
+    pub fn weights(&self, fan_in: usize, fan_out: usize, rng: &mut impl Rng) -> Matrix<f64> {
+        let (fan_in_f, fan_out_f) = (fan_in.max(1) as f64, fan_out.max(1) as f64);
+
+        match *self {
+            Initializer::XavierUniform => uniform(fan_in, fan_out, 2.0 / (fan_in_f + fan_out_f), rng),
+            Initializer::HeUniform => uniform(fan_in, fan_out, 2.0 / fan_in_f, rng),
+            Initializer::LeCunUniform => uniform(fan_in, fan_out, 1.0 / fan_in_f, rng),
+            Initializer::XavierNormal => normal(fan_in, fan_out, (2.0 / (fan_in_f + fan_out_f)).sqrt(), rng),
+            Initializer::HeNormal => normal(fan_in, fan_out, (2.0 / fan_in_f).sqrt(), rng),
+            Initializer::LeCunNormal => normal(fan_in, fan_out, (1.0 / fan_in_f).sqrt(), rng),
+            Initializer::Orthogonal { gain } => orthogonal(fan_in, fan_out, gain, rng),
+            Initializer::Uniform { low, high } => {
+                assert!(low <= high, "Uniform initializer bounds must satisfy low <= high.");
+                Matrix::from_fn(fan_out, fan_in, |_, _| rng.gen_range(low..=high))
+            },
+            Initializer::Zeros => Matrix::from_fn(fan_out, fan_in, |_, _| 0.0),
+            Initializer::Constant(c) => Matrix::from_fn(fan_out, fan_in, |_, _| c),
+        }
+    }
+
+}
+
+// Box–Muller
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    let u: f64 = 1.0 - rng.gen::<f64>();
+    let v: f64 = rng.gen();
+    (-2.0 * u.ln()).sqrt() * (2.0 * PI * v).cos()
+}
+
+// symmetric, with the given variance
+fn uniform(fan_in: usize, fan_out: usize, variance: f64, rng: &mut impl Rng) -> Matrix<f64> {
+    let limit = (3.0 * variance).sqrt();
+    Matrix::from_fn(fan_out, fan_in, |_, _| rng.gen_range(-limit..=limit))
+}
+
+fn normal(fan_in: usize, fan_out: usize, std_dev: f64, rng: &mut impl Rng) -> Matrix<f64> {
+    Matrix::from_fn(fan_out, fan_in, |_, _| std_dev * standard_normal(rng))
+}
+
+// the orthogonal factor of a Gaussian matrix, with the column signs fixed by
+// R's diagonal so the result is uniformly distributed
+fn orthogonal(fan_in: usize, fan_out: usize, gain: f64, rng: &mut impl Rng) -> Matrix<f64> {
+    let (rows, cols) = (fan_out.max(fan_in), fan_out.min(fan_in));
+    let qr = normal(cols, rows, 1.0, rng).qr();
+    let (q, r) = (qr.q(), qr.r());
+
+    let tall = Matrix::from_fn(rows, cols, |i, j| gain * q[(i, j)] * r[(j, j)].signum());
+
+    if fan_out >= fan_in { tall } else { tall.transpose() }
+}
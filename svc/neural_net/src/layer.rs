@@ -1,48 +1,227 @@
 use crate::activations::ActivationFunction;
+use crate::init::Initializer;
 use maths::{Matrix, Vector};
 use rand::Rng;
 
 
-pub struct Layer {
+/// Gradients of the loss with respect to a layer's parameters, in the order
+/// `Layer::parameters` lists them and summed over the batch, and with
+/// respect to its input, one row per sample.
+pub struct Gradients {
+    pub parameters: Vec<Vector<f64>>,
+    pub input: Matrix<f64>,
+}
+
+/// A stage of a `NeuralNetwork`, working on batches with one row per sample.
+/// `forward` caches whatever `backward` needs, so each `backward` refers to
+/// the most recent `forward`.
+pub trait Layer {
+
+    /// The layer's output, without caching anything for `backward`.
+    fn predict(&self, input: &Matrix<f64>) -> Matrix<f64>;
+
+    fn forward(&mut self, input: &Matrix<f64>) -> Matrix<f64>;
+
+    /// Backpropagates `output_gradient`, the loss gradient with respect to
+    /// the output of the last `forward`.
+    fn backward(&mut self, output_gradient: &Matrix<f64>) -> Gradients;
+
+    /// Trainable parameters, each flattened; empty for layers without any.
+    fn parameters(&self) -> Vec<&[f64]>;
+
+    fn parameters_mut(&mut self) -> Vec<&mut [f64]>;
+
+    /// The length of the layer's output for one sample.
+    fn output_shape(&self) -> usize;
+
+}
+
+
+/// What `Dense::backward` needs from the last `forward` call.
+struct Cache {
+    input: Matrix<f64>,
+    // pre-activation `input * weightsᵀ + biases`
+    z: Matrix<f64>,
+}
+
+/// A fully connected layer, `activation(weights * input + biases)` for each
+/// sample.
+pub struct Dense {
     pub weights: Matrix<f64>,
     pub biases: Vector<f64>,
     pub activation: ActivationFunction,
+    cache: Option<Cache>,
 }
 
-impl Layer {
+impl Dense {
 
-    pub fn new(input_size: usize, output_size: usize, activation: ActivationFunction) -> Self {
+    /// Weights drawn from `rng` with the usual initializer for `activation`,
+    /// zero biases.
+    pub fn new(input_size: usize, output_size: usize, activation: ActivationFunction, rng: &mut impl Rng) -> Self {
+        Dense::with_initializer(input_size, output_size, activation, Initializer::for_activation(activation), rng)
+    }
 
-        let mut rng = rand::thread_rng();
-        let weights = Matrix::from_fn(output_size, input_size, |_, _| rng.gen_range(-1.0..1.0));
-        let biases = Vector::from_fn(output_size, |_| rng.gen_range(-1.0..1.0));
+    pub fn with_initializer(
+        input_size: usize,
+        output_size: usize,
+        activation: ActivationFunction,
+        initializer: Initializer,
+        rng: &mut impl Rng,
+    ) -> Self {
 
-        Layer {
-            weights,
-            biases,
+        Dense {
+            weights: initializer.weights(input_size, output_size, rng),
+            biases: Vector::new(vec![0.0; output_size]),
             activation,
+            cache: None,
+        }
+
+    }
+
+    fn pre_activation(&self, input: &Matrix<f64>) -> Matrix<f64> {
+        let mut z = input * &self.weights.transpose();
+        for row in z.rows_mut() {
+            for (x, b) in row.iter_mut().zip(&self.biases) {
+                *x += b;
+            }
+        }
+        z
+    }
+
+    fn activate(&self, z: &Matrix<f64>) -> Matrix<f64> {
+        let elements = z
+            .rows()
+            .flat_map(|row| self.activation.activate_vector(&Vector::new(row.to_vec())).elements)
+            .collect::<Vec<_>>();
+
+        Matrix::new(z.rows, z.cols, elements)
+    }
+
+}
+
+impl Layer for Dense {
+
+    fn predict(&self, input: &Matrix<f64>) -> Matrix<f64> {
+        self.activate(&self.pre_activation(input))
+    }
+
+    fn forward(&mut self, input: &Matrix<f64>) -> Matrix<f64> {
+        let z = self.pre_activation(input);
+        let output = self.activate(&z);
+
+        self.cache = Some(Cache {
+            input: input.clone(),
+            z,
+        });
+
+        output
+    }
+
+    fn backward(&mut self, output_gradient: &Matrix<f64>) -> Gradients {
+        let cache = self.cache.as_ref().expect("Dense::backward called before forward.");
+
+        // one Jacobian product per sample, as vector activations couple
+        // a sample's outputs
+        let delta = Matrix::new(
+            cache.z.rows,
+            cache.z.cols,
+            cache.z
+                .rows()
+                .zip(output_gradient.rows())
+                .flat_map(|(z, g)| {
+                    self.activation.backward(&Vector::new(z.to_vec()), &Vector::new(g.to_vec())).elements
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        let weights = &delta.transpose() * &cache.input;
+        let biases = delta.cols().map(|col| col.sum()).collect();
+
+        Gradients {
+            input: &delta * &self.weights,
+            parameters: vec![Vector::new(weights.elements), biases],
         }
+    }
+
+    /// The weights, row-major, then the biases.
+    fn parameters(&self) -> Vec<&[f64]> {
+        vec![self.weights.as_slice(), &self.biases.elements]
+    }
 
+    fn parameters_mut(&mut self) -> Vec<&mut [f64]> {
+        vec![self.weights.as_mut_slice(), &mut self.biases.elements]
     }
 
-    pub fn forward(&self, input: &Vector<f64>) -> Vector<f64> {
-        let z = &(&self.weights * input) + &self.biases;
-        z.map(|x| self.activation.activate(*x))
+    fn output_shape(&self) -> usize {
+        self.biases.len()
     }
 
-    pub fn backward(
-        &self,
-        input: &Vector<f64>,
-        delta: &Vector<f64>,
-    ) -> (Matrix<f64>, Vector<f64>, Vector<f64>) {
+}
+
+
+/// Inverted dropout: while training, zeroes each input with probability
+/// `rate` and scales the rest by `1 / (1 - rate)`; `predict` passes inputs
+/// through unchanged. The masks are drawn from the layer's own `rng`.
+pub struct Dropout<R> {
+    pub rate: f64,
+    size: usize,
+    rng: R,
+    // the last `forward`'s mask, already scaled
+    mask: Option<Matrix<f64>>,
+}
 
-        let _activation_derivative = input.map(|x| self.activation.derivative(*x));
-        let delta_weights = delta.outer(input);
-        let delta_biases = delta.clone();
-        let delta_input = &self.weights.transpose() * delta;
+impl<R: Rng> Dropout<R> {
 
-        (delta_weights, delta_biases, delta_input)
+    pub fn new(size: usize, rate: f64, rng: R) -> Self {
+        assert!((0.0..1.0).contains(&rate), "Dropout rate must be in [0, 1).");
+
+        Dropout {
+            rate,
+            size,
+            rng,
+            mask: None,
+        }
     }
 
 }
 
+impl<R: Rng> Layer for Dropout<R> {
+
+    fn predict(&self, input: &Matrix<f64>) -> Matrix<f64> {
+        input.clone()
+    }
+
+    fn forward(&mut self, input: &Matrix<f64>) -> Matrix<f64> {
+        let scale = 1.0 / (1.0 - self.rate);
+        let mask = Matrix::from_fn(input.rows, input.cols, |_, _| {
+            if self.rng.gen::<f64>() < self.rate { 0.0 } else { scale }
+        });
+
+        let output = Matrix::new(input.rows, input.cols, input.iter().zip(&mask).map(|(x, m)| x * m).collect::<Vec<_>>());
+        self.mask = Some(mask);
+
+        output
+    }
+
+    fn backward(&mut self, output_gradient: &Matrix<f64>) -> Gradients {
+        let mask = self.mask.as_ref().expect("Dropout::backward called before forward.");
+
+        Gradients {
+            parameters: vec![],
+            input: Matrix::new(mask.rows, mask.cols, output_gradient.iter().zip(mask).map(|(g, m)| g * m).collect::<Vec<_>>()),
+        }
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        vec![]
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut [f64]> {
+        vec![]
+    }
+
+    fn output_shape(&self) -> usize {
+        self.size
+    }
+
+}
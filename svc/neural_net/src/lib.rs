@@ -1,17 +1,64 @@
+//! Randomness (weight initialisation, shuffling, dropout masks) only comes
+//! from RNGs passed in by the caller, so runs seeded the same way produce
+//! bit-identical weights on the same platform.
+
 pub mod activations;
+pub mod init;
 pub mod layer;
 pub mod loss;
+pub mod optim;
+pub mod schedule;
 
 
-pub use crate::layer::Layer;
+pub use crate::layer::{Dense, Dropout, Gradients, Layer};
 use crate::loss::LossFunction;
+use crate::optim::Optimizer;
+use crate::schedule::LrScheduler;
 use log::debug;
-use maths::Vector;
+use maths::{Matrix, Vector};
+use rand::{seq::SliceRandom, Rng};
+
+
+
+#[derive(Debug, Clone)]
+pub struct TrainOptions<'a> {
+    pub epochs: usize,
+    pub batch_size: usize,
+    /// Whether to shuffle the samples at the start of each epoch.
+    pub shuffle: bool,
+    /// Inputs and targets evaluated after each epoch; their loss drives
+    /// the learning rate schedule in place of the training loss.
+    pub validation: Option<(&'a Matrix<f64>, &'a Matrix<f64>)>,
+    /// One weight per training sample scaling its loss and gradient, e.g.
+    /// to favour recent observations; `None` weighs every sample as 1.
+    pub sample_weights: Option<&'a [f64]>,
+}
 
+impl Default for TrainOptions<'_> {
+    fn default() -> Self {
+        TrainOptions {
+            epochs: 1,
+            batch_size: 32,
+            shuffle: true,
+            validation: None,
+            sample_weights: None,
+        }
+    }
+}
 
+/// Per-epoch records from `NeuralNetwork::train_batches`.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    /// Mean training loss, each batch's measured before its update and
+    /// weighted by `TrainOptions::sample_weights`.
+    pub loss: Vec<f64>,
+    /// Empty without `TrainOptions::validation`.
+    pub validation_loss: Vec<f64>,
+    pub learning_rates: Vec<f64>,
+}
 
 pub struct NeuralNetwork {
-    pub layers: Vec<Layer>,
+    pub layers: Vec<Box<dyn Layer>>,
     pub loss: LossFunction,
 }
 
@@ -24,87 +71,181 @@ impl NeuralNetwork {
         }
     }
 
-    pub fn add_layer(&mut self, layer: Layer) {
-        self.layers.push(layer);
+    pub fn add_layer(&mut self, layer: impl Layer + 'static) {
+        self.layers.push(Box::new(layer));
     }
 
     pub fn predict(&self, input: &Vector<f64>) -> Vector<f64> {
-        let mut output = input.clone();
+        let output = self.predict_batch(&Matrix::new(1, input.len(), input.elements.clone()));
+        Vector::new(output.elements)
+    }
+
+    /// Predictions for a batch of inputs, one row per sample.
+    pub fn predict_batch(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        let mut output = inputs.clone();
 
         for layer in &self.layers {
-            output = layer.forward(&output);
+            output = layer.predict(&output);
         }
 
         output
     }
 
+    /// Trains on one sample at a time, in order.
     pub fn train(
         &mut self,
         inputs: &[Vector<f64>],
         targets: &[Vector<f64>],
-        learning_rate: f64,
+        optimizer: &mut impl Optimizer,
         epochs: usize,
     ) {
 
+        let as_row = |v: &Vector<f64>| Matrix::new(1, v.len(), v.elements.clone());
+
         for epoch in 0..epochs {
             for (input, target) in inputs.iter().zip(targets) {
 
-                let mut activations = Vec::new();
-                let mut z_values = Vec::new();
-                let mut output = input.clone();
+                let loss = self.step(&as_row(input), &as_row(target), None, optimizer);
 
-                activations.push(output.clone());
+                // print loss every 1k epochs
+                if epoch % 1000 == 0 {
+                    debug!("Epoch: {}, Current loss: {}", epoch, loss)
+                }
+
+            }
+        }
 
-                for layer in &self.layers {
+    }
 
-                    let weighted_input = &layer.weights * &output;
-                    let z = &weighted_input + &layer.biases;
+    /// Mean loss over `inputs` and `targets`, one row per sample.
+    pub fn evaluate(&self, inputs: &Matrix<f64>, targets: &Matrix<f64>) -> f64 {
+        let outputs = self.predict_batch(inputs);
 
-                    z_values.push(z.clone());
-                    output = z.map(|x| layer.activation.activate(*x));
-                    activations.push(output.clone());
+        let total: f64 = outputs
+            .rows()
+            .zip(targets.rows())
+            .map(|(output, target)| self.loss.loss(&Vector::new(output.to_vec()), &Vector::new(target.to_vec())))
+            .sum();
 
-                }
+        total / inputs.rows.max(1) as f64
+    }
+
+    /// Mini-batch training over `inputs` and `targets`, one row per sample:
+    /// one `optimizer` step per batch with the gradients averaged over it.
+    /// `rng` shuffles the samples each epoch, and `scheduler`, if given, sets
+    /// the optimizer's learning rate at the start of each.
+    pub fn train_batches(
+        &mut self,
+        inputs: &Matrix<f64>,
+        targets: &Matrix<f64>,
+        optimizer: &mut impl Optimizer,
+        mut scheduler: Option<&mut dyn LrScheduler>,
+        options: &TrainOptions,
+        rng: &mut impl Rng,
+    ) -> History {
+
+        assert_eq!(inputs.rows, targets.rows, "Inputs and targets must have the same number of samples.");
+        assert!(options.batch_size > 0, "Batch size must be positive.");
+        if let Some(weights) = options.sample_weights {
+            assert_eq!(weights.len(), inputs.rows, "Expected one weight per sample.");
+        }
+
+        let mut order: Vec<usize> = (0..inputs.rows).collect();
+        let mut history = History::default();
+        let mut last_loss = None;
 
-                let mut delta = self.loss.derivative(&output, target);
+        for epoch in 0..options.epochs {
 
-                for (i, layer) in self.layers.iter_mut().rev().enumerate() {
+            if let Some(scheduler) = scheduler.as_mut() {
+                optimizer.set_learning_rate(scheduler.learning_rate(epoch, last_loss));
+            }
+            history.learning_rates.push(optimizer.learning_rate());
 
-                    let z = &z_values[z_values.len() - i - 1];
-                    let activation_derivative = z.map(|x| layer.activation.derivative(*x));
+            if options.shuffle {
+                order.shuffle(rng);
+            }
 
-                    delta = delta.element_wise_mul(&activation_derivative);
+            let mut total = 0.0;
+            for batch in order.chunks(options.batch_size) {
+                let weights = options.sample_weights.map(|w| batch.iter().map(|&i| w[i]).collect::<Vec<_>>());
+                let loss = self.step(&gather(inputs, batch), &gather(targets, batch), weights.as_deref(), optimizer);
+                total += loss * batch.len() as f64;
+            }
 
-                    let input_activation = &activations[activations.len() - i - 2];
-                    let delta_weights = delta.outer(input_activation);
-                    let delta_biases = delta.clone();
+            let loss = total / inputs.rows.max(1) as f64;
+            debug!("Epoch: {}, Mean loss: {}", epoch, loss);
+            history.loss.push(loss);
+            last_loss = Some(loss);
 
-                    debug!("Layer {}: delta size = {}, input_activation size = {}", i, delta.elements.len(), input_activation.elements.len());
-                    debug!("delta_weights dimensions: ({}, {})", delta_weights.rows, delta_weights.cols);
-                    debug!("layer.weights dimensions: ({}, {})", layer.weights.rows, layer.weights.cols);
+            if let Some((inputs, targets)) = options.validation {
+                let loss = self.evaluate(inputs, targets);
+                debug!("Epoch: {}, Validation loss: {}", epoch, loss);
+                history.validation_loss.push(loss);
+                last_loss = Some(loss);
+            }
 
+        }
 
-                    layer.weights = &layer.weights - &(&delta_weights * learning_rate);
-                    layer.biases = &layer.biases - &(&delta_biases * learning_rate);
+        history
+    }
 
-                    delta = &layer.weights.transpose() * &delta;
+    // forward and backward over one batch and an optimizer step with the
+    // averaged gradients, returning the batch's mean loss before the step;
+    // `weights` scales each sample's loss and gradient
+    fn step(
+        &mut self,
+        inputs: &Matrix<f64>,
+        targets: &Matrix<f64>,
+        weights: Option<&[f64]>,
+        optimizer: &mut impl Optimizer,
+    ) -> f64 {
+
+        let mut output = inputs.clone();
+        for layer in &mut self.layers {
+            output = layer.forward(&output);
+        }
 
-                }
+        let samples = output.rows as f64;
+        let mut loss = 0.0;
+        let mut gradient = Vec::with_capacity(output.elements.len());
 
-                let current_loss = self.loss.loss(&self.predict(input), target);
+        for (i, (output, target)) in output.rows().zip(targets.rows()).enumerate() {
+            let (output, target) = (Vector::new(output.to_vec()), Vector::new(target.to_vec()));
+            let weight = weights.map_or(1.0, |w| w[i]);
+            loss += weight * self.loss.loss(&output, &target);
+            gradient.extend(self.loss.derivative(&output, &target).iter().map(|g| weight * g / samples));
+        }
 
-                // print loss every 1k epochs
-                if epoch % 1000 == 0 {
-                    debug!("Epoch: {}, Current loss: {}", epoch, current_loss)
-                }
+        let mut gradient = Matrix::new(output.rows, output.cols, gradient);
+        let mut layer_gradients = Vec::with_capacity(self.layers.len());
+
+        for (i, layer) in self.layers.iter_mut().rev().enumerate() {
+
+            let gradients = layer.backward(&gradient);
+
+            debug!("Layer {}: {} parameter gradients", i, gradients.parameters.len());
+
+            gradient = gradients.input;
+            layer_gradients.push(gradients.parameters);
 
-            }
         }
 
-    }
+        let gradients: Vec<Vector<f64>> = layer_gradients.into_iter().rev().flatten().collect();
+        let parameters = self.layers.iter_mut().flat_map(|layer| layer.parameters_mut()).collect();
+        optimizer.step(parameters, &gradients);
 
+        loss / samples
+    }
 
 }
 
+// rows `indices` of `matrix`, in that order
+fn gather(matrix: &Matrix<f64>, indices: &[usize]) -> Matrix<f64> {
+    let elements = indices
+        .iter()
+        .flat_map(|&row| &matrix.elements[row * matrix.cols..(row + 1) * matrix.cols])
+        .copied()
+        .collect();
 
-
+    Matrix::new(indices.len(), matrix.cols, elements)
+}
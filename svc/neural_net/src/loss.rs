@@ -1,13 +1,134 @@
+
+use crate::activations::log_softmax;
 use maths::Vector;
+use std::f64::consts::LN_2;
 
 
+/// Losses for a single sample. `derivative` is the gradient of `loss` with
+/// respect to `output`, except where noted.
+///
+/// Classification targets are one-hot (or soft) class probabilities.
+/// `weights`, where present, holds one weight per class (per output), and
+/// `None` weighs every class as 1.
+///
+/// The regression losses average over the outputs like `MeanSquaredError`;
+/// per-sample weights are applied by training, see
+/// `TrainOptions::sample_weights`.
+#[derive(Debug, Clone, PartialEq)]
 pub enum LossFunction {
+    /// The mean squared error; its `derivative`, `output - target`, is the
+    /// gradient of half the summed squared error.
     MeanSquaredError,
+    MeanAbsoluteError,
+    /// Squared for residuals within `delta`, absolute beyond it:
+    /// `r² / 2` or `delta (|r| - delta / 2)`.
+    Huber {
+        delta: f64,
+    },
+    /// `ln cosh(output - target)`, quadratic near zero and absolute far out.
+    LogCosh,
+    /// Pinball loss for the `tau` quantile, `max(τ r, (τ - 1) r)` of the
+    /// residual `r = target - output`.
+    Quantile {
+        tau: f64,
+    },
+    /// `-Σ w_c t_c ln p_c` of output probabilities, e.g. from a softmax.
+    CrossEntropy {
+        weights: Option<Vec<f64>>,
+    },
+    /// Cross-entropy of `softmax(output)`, taking logits; more stable than
+    /// a separate softmax followed by `CrossEntropy`.
+    SoftmaxCrossEntropy {
+        weights: Option<Vec<f64>>,
+    },
+    /// Independent yes/no probabilities per output, e.g. from sigmoids.
+    BinaryCrossEntropy {
+        weights: Option<Vec<f64>>,
+    },
+    /// Binary cross-entropy of `sigmoid(output)`, taking logits.
+    BinaryCrossEntropyWithLogits {
+        weights: Option<Vec<f64>>,
+    },
+    /// Multi-class hinge on scores, `Σ_{j ≠ y} max(0, 1 + s_j - s_y)` for the
+    /// target class `y`, weighted by `w_y`.
+    Hinge {
+        weights: Option<Vec<f64>>,
+    },
+    /// Cross-entropy down-weighting well classified samples,
+    /// `-Σ w_c t_c (1 - p_c)^γ ln p_c` of output probabilities.
+    Focal {
+        gamma: f64,
+        weights: Option<Vec<f64>>,
+    },
+}
+
+// keeps logarithms of probabilities finite
+const MIN_PROBABILITY: f64 = 1e-12;
+
+fn weight(weights: &Option<Vec<f64>>, class: usize) -> f64 {
+    weights.as_ref().map_or(1.0, |w| w[class])
+}
+
+fn sigmoid(z: f64) -> f64 {
+    if z >= 0.0 {
+        1.0 / (1.0 + (-z).exp())
+    } else {
+        let e = z.exp();
+        e / (1.0 + e)
+    }
+}
+
+// ln cosh x without overflow, |x| + ln(1 + e^-2|x|) - ln 2
+fn log_cosh(x: f64) -> f64 {
+    x.abs() + (-2.0 * x.abs()).exp().ln_1p() - LN_2
+}
+
+// the mean of `f(output - target)`
+fn mean_residual(output: &Vector<f64>, target: &Vector<f64>, f: impl Fn(f64) -> f64) -> f64 {
+    output.iter().zip(target).map(|(o, t)| f(o - t)).sum::<f64>() / output.len() as f64
+}
+
+// `f'(output - target)` averaged over the outputs
+fn residual_gradient(output: &Vector<f64>, target: &Vector<f64>, f: impl Fn(f64) -> f64) -> Vector<f64> {
+    let n = output.len() as f64;
+    Vector::from_fn(output.len(), |i| f(output[i] - target[i]) / n)
+}
+
+// the target class of one-hot `target`
+fn class(target: &Vector<f64>) -> usize {
+    target
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map_or(0, |(c, _)| c)
 }
 
 impl LossFunction {
 
+    fn class_weights(&self) -> Option<&[f64]> {
+        match self {
+            LossFunction::CrossEntropy { weights }
+            | LossFunction::SoftmaxCrossEntropy { weights }
+            | LossFunction::BinaryCrossEntropy { weights }
+            | LossFunction::BinaryCrossEntropyWithLogits { weights }
+            | LossFunction::Hinge { weights }
+            | LossFunction::Focal { weights, .. } => weights.as_deref(),
+            _ => None,
+        }
+    }
+
+    fn check_weights(&self, output: &Vector<f64>) {
+        if let Some(weights) = self.class_weights() {
+            assert_eq!(weights.len(), output.len(), "Class weights must have one weight per output.");
+        }
+    }
+
+    /// # Panics
+    ///
+    /// If the class weights don't have one weight per output.
     pub fn loss(&self, output: &Vector<f64>, target: &Vector<f64>) -> f64 {
+        self.check_weights(output);
+
         match self {
             LossFunction::MeanSquaredError => {
                 output.elements
@@ -16,16 +137,139 @@ impl LossFunction {
                     .map(|(o, t)| (o - t).powi(2))
                     .sum::<f64>() / output.elements.len() as f64
             },
+            LossFunction::MeanAbsoluteError => mean_residual(output, target, f64::abs),
+            LossFunction::Huber { delta } => mean_residual(output, target, |r| {
+                if r.abs() <= *delta { 0.5 * r * r } else { delta * (r.abs() - 0.5 * delta) }
+            }),
+            LossFunction::LogCosh => mean_residual(output, target, log_cosh),
+            LossFunction::Quantile { tau } => mean_residual(output, target, |r| {
+                // r is output - target here
+                if r < 0.0 { -tau * r } else { (1.0 - tau) * r }
+            }),
+            LossFunction::CrossEntropy { weights } => {
+                output.iter()
+                    .zip(target)
+                    .enumerate()
+                    .map(|(c, (p, t))| -weight(weights, c) * t * p.max(MIN_PROBABILITY).ln())
+                    .sum()
+            },
+            LossFunction::SoftmaxCrossEntropy { weights } => {
+                log_softmax(output).iter()
+                    .zip(target)
+                    .enumerate()
+                    .map(|(c, (log_p, t))| -weight(weights, c) * t * log_p)
+                    .sum()
+            },
+            LossFunction::BinaryCrossEntropy { weights } => {
+                output.iter()
+                    .zip(target)
+                    .enumerate()
+                    .map(|(c, (p, t))| {
+                        let p = p.clamp(MIN_PROBABILITY, 1.0 - MIN_PROBABILITY);
+                        -weight(weights, c) * (t * p.ln() + (1.0 - t) * (1.0 - p).ln())
+                    })
+                    .sum()
+            },
+            LossFunction::BinaryCrossEntropyWithLogits { weights } => {
+                // max(z, 0) - z t + ln(1 + e^-|z|), which never overflows
+                output.iter()
+                    .zip(target)
+                    .enumerate()
+                    .map(|(c, (z, t))| weight(weights, c) * (z.max(0.0) - z * t + (-z.abs()).exp().ln_1p()))
+                    .sum()
+            },
+            LossFunction::Hinge { weights } => {
+                let y = class(target);
+                let margins: f64 = output.iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != y)
+                    .map(|(_, s)| (1.0 + s - output[y]).max(0.0))
+                    .sum();
+                weight(weights, y) * margins
+            },
+            LossFunction::Focal { gamma, weights } => {
+                output.iter()
+                    .zip(target)
+                    .enumerate()
+                    .map(|(c, (p, t))| {
+                        let p = p.clamp(MIN_PROBABILITY, 1.0);
+                        -weight(weights, c) * t * (1.0 - p).powf(*gamma) * p.ln()
+                    })
+                    .sum()
+            },
         }
     }
 
+    /// # Panics
+    ///
+    /// As `loss`.
     pub fn derivative(&self, output: &Vector<f64>, target: &Vector<f64>) -> Vector<f64> {
+        self.check_weights(output);
+
         match self {
             LossFunction::MeanSquaredError => {
                 output - target
-            }
+            },
+            LossFunction::MeanAbsoluteError => {
+                residual_gradient(output, target, |r| if r == 0.0 { 0.0 } else { r.signum() })
+            },
+            LossFunction::Huber { delta } => {
+                residual_gradient(output, target, |r| r.clamp(-delta, *delta))
+            },
+            LossFunction::LogCosh => {
+                residual_gradient(output, target, f64::tanh)
+            },
+            LossFunction::Quantile { tau } => {
+                residual_gradient(output, target, |r| {
+                    if r < 0.0 { -tau } else if r > 0.0 { 1.0 - tau } else { 0.0 }
+                })
+            },
+            LossFunction::CrossEntropy { weights } => {
+                Vector::from_fn(output.len(), |c| -weight(weights, c) * target[c] / output[c].max(MIN_PROBABILITY))
+            },
+            LossFunction::SoftmaxCrossEntropy { weights } => {
+                // Σ_c w_c t_c (softmax_j - δ_cj)
+                let total: f64 = target.iter().enumerate().map(|(c, t)| weight(weights, c) * t).sum();
+                let probabilities = log_softmax(output).map(|log_p| log_p.exp());
+                Vector::from_fn(output.len(), |j| total * probabilities[j] - weight(weights, j) * target[j])
+            },
+            LossFunction::BinaryCrossEntropy { weights } => {
+                Vector::from_fn(output.len(), |c| {
+                    let p = output[c].clamp(MIN_PROBABILITY, 1.0 - MIN_PROBABILITY);
+                    weight(weights, c) * (p - target[c]) / (p * (1.0 - p))
+                })
+            },
+            LossFunction::BinaryCrossEntropyWithLogits { weights } => {
+                Vector::from_fn(output.len(), |c| weight(weights, c) * (sigmoid(output[c]) - target[c]))
+            },
+            LossFunction::Hinge { weights } => {
+                let y = class(target);
+                let w = weight(weights, y);
+                let mut gradient = Vector::new(vec![0.0; output.len()]);
+                for j in (0..output.len()).filter(|&j| j != y) {
+                    if 1.0 + output[j] - output[y] > 0.0 {
+                        gradient[j] += w;
+                        gradient[y] -= w;
+                    }
+                }
+                gradient
+            },
+            LossFunction::Focal { gamma, weights } => {
+                Vector::from_fn(output.len(), |c| {
+                    let p = output[c].clamp(MIN_PROBABILITY, 1.0);
+                    let q = 1.0 - p;
+                    if *gamma == 0.0 {
+                        return -weight(weights, c) * target[c] / p;
+                    }
+                    if q == 0.0 {
+                        return 0.0;
+                    }
+                    // d/dp of -(1 - p)^γ ln p
+                    let d = gamma * q.powf(gamma - 1.0) * p.ln() - q.powf(*gamma) / p;
+                    weight(weights, c) * target[c] * d
+                })
+            },
         }
     }
 
 }
-
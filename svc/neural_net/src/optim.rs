@@ -0,0 +1,285 @@
+
+use maths::Vector;
+
+
+/// Updates a network's parameters from their gradients, keeping whatever
+/// per-parameter state (velocities, moments) the method needs.
+pub trait Optimizer {
+
+    /// One update of every parameter. Parameters come in the same order on
+    /// every call, which is how each is matched with its state.
+    ///
+    /// # Panics
+    ///
+    /// If the parameters' number or lengths differ from the first call's.
+    fn step(&mut self, parameters: Vec<&mut [f64]>, gradients: &[Vector<f64>]);
+
+    fn learning_rate(&self) -> f64;
+
+    /// Used by learning rate schedules; keeps all other state.
+    fn set_learning_rate(&mut self, learning_rate: f64);
+
+}
+
+// one zeroed buffer per parameter, allocated on the first step
+fn init_state(state: &mut Vec<Vec<f64>>, parameters: &[&mut [f64]]) {
+    if state.is_empty() {
+        *state = parameters.iter().map(|p| vec![0.0; p.len()]).collect();
+    }
+    assert!(
+        state.len() == parameters.len() && state.iter().zip(parameters).all(|(s, p)| s.len() == p.len()),
+        "Parameter shapes changed between optimizer steps; use a new optimizer for different parameters.",
+    );
+}
+
+
+/// Stochastic gradient descent, optionally with (Nesterov) momentum.
+#[derive(Debug, Clone)]
+pub struct Sgd {
+    pub learning_rate: f64,
+    pub momentum: f64,
+    pub nesterov: bool,
+    velocity: Vec<Vec<f64>>,
+}
+
+impl Sgd {
+
+    pub fn new(learning_rate: f64) -> Self {
+        Sgd::momentum(learning_rate, 0.0)
+    }
+
+    pub fn momentum(learning_rate: f64, momentum: f64) -> Self {
+        Sgd {
+            learning_rate,
+            momentum,
+            nesterov: false,
+            velocity: Vec::new(),
+        }
+    }
+
+    pub fn nesterov(learning_rate: f64, momentum: f64) -> Self {
+        Sgd {
+            nesterov: true,
+            ..Sgd::momentum(learning_rate, momentum)
+        }
+    }
+
+}
+
+impl Optimizer for Sgd {
+
+    fn step(&mut self, parameters: Vec<&mut [f64]>, gradients: &[Vector<f64>]) {
+        init_state(&mut self.velocity, &parameters);
+
+        for ((parameter, gradient), velocity) in parameters.into_iter().zip(gradients).zip(&mut self.velocity) {
+            for ((p, &g), v) in parameter.iter_mut().zip(gradient).zip(velocity) {
+                *v = self.momentum * *v + g;
+                let direction = if self.nesterov { g + self.momentum * *v } else { *v };
+                *p -= self.learning_rate * direction;
+            }
+        }
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
+}
+
+
+/// Adam, with bias-corrected first and second moment estimates.
+#[derive(Debug, Clone)]
+pub struct Adam {
+    pub learning_rate: f64,
+    pub beta1: f64,
+    pub beta2: f64,
+    pub epsilon: f64,
+    steps: i32,
+    first_moments: Vec<Vec<f64>>,
+    second_moments: Vec<Vec<f64>>,
+}
+
+impl Adam {
+
+    pub fn new(learning_rate: f64) -> Self {
+        Adam {
+            learning_rate,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+            steps: 0,
+            first_moments: Vec::new(),
+            second_moments: Vec::new(),
+        }
+    }
+
+}
+
+impl Optimizer for Adam {
+
+    fn step(&mut self, parameters: Vec<&mut [f64]>, gradients: &[Vector<f64>]) {
+        init_state(&mut self.first_moments, &parameters);
+        init_state(&mut self.second_moments, &parameters);
+
+        self.steps += 1;
+        let first_correction = 1.0 - self.beta1.powi(self.steps);
+        let second_correction = 1.0 - self.beta2.powi(self.steps);
+
+        let state = self.first_moments.iter_mut().zip(&mut self.second_moments);
+        for ((parameter, gradient), (first, second)) in parameters.into_iter().zip(gradients).zip(state) {
+            for (i, (p, &g)) in parameter.iter_mut().zip(gradient).enumerate() {
+                first[i] = self.beta1 * first[i] + (1.0 - self.beta1) * g;
+                second[i] = self.beta2 * second[i] + (1.0 - self.beta2) * g * g;
+
+                let m = first[i] / first_correction;
+                let v = second[i] / second_correction;
+                *p -= self.learning_rate * m / (v.sqrt() + self.epsilon);
+            }
+        }
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
+}
+
+
+/// Adam with decoupled weight decay: parameters shrink by
+/// `learning_rate * weight_decay` of themselves each step, independently of
+/// the gradient.
+#[derive(Debug, Clone)]
+pub struct AdamW {
+    pub adam: Adam,
+    pub weight_decay: f64,
+}
+
+impl AdamW {
+
+    pub fn new(learning_rate: f64, weight_decay: f64) -> Self {
+        AdamW {
+            adam: Adam::new(learning_rate),
+            weight_decay,
+        }
+    }
+
+}
+
+impl Optimizer for AdamW {
+
+    fn step(&mut self, mut parameters: Vec<&mut [f64]>, gradients: &[Vector<f64>]) {
+        let decay = 1.0 - self.adam.learning_rate * self.weight_decay;
+        for p in parameters.iter_mut().flat_map(|p| p.iter_mut()) {
+            *p *= decay;
+        }
+
+        self.adam.step(parameters, gradients);
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.adam.learning_rate
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.adam.learning_rate = learning_rate;
+    }
+
+}
+
+
+/// RMSProp: steps scaled by a running average of squared gradients.
+#[derive(Debug, Clone)]
+pub struct RmsProp {
+    pub learning_rate: f64,
+    pub decay: f64,
+    pub epsilon: f64,
+    mean_squares: Vec<Vec<f64>>,
+}
+
+impl RmsProp {
+
+    pub fn new(learning_rate: f64) -> Self {
+        RmsProp {
+            learning_rate,
+            decay: 0.9,
+            epsilon: 1e-8,
+            mean_squares: Vec::new(),
+        }
+    }
+
+}
+
+impl Optimizer for RmsProp {
+
+    fn step(&mut self, parameters: Vec<&mut [f64]>, gradients: &[Vector<f64>]) {
+        init_state(&mut self.mean_squares, &parameters);
+
+        for ((parameter, gradient), mean_square) in parameters.into_iter().zip(gradients).zip(&mut self.mean_squares) {
+            for ((p, &g), s) in parameter.iter_mut().zip(gradient).zip(mean_square) {
+                *s = self.decay * *s + (1.0 - self.decay) * g * g;
+                *p -= self.learning_rate * g / (s.sqrt() + self.epsilon);
+            }
+        }
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
+}
+
+
+/// Adagrad: steps scaled by the accumulated sum of squared gradients.
+#[derive(Debug, Clone)]
+pub struct Adagrad {
+    pub learning_rate: f64,
+    pub epsilon: f64,
+    sums: Vec<Vec<f64>>,
+}
+
+impl Adagrad {
+
+    pub fn new(learning_rate: f64) -> Self {
+        Adagrad {
+            learning_rate,
+            epsilon: 1e-10,
+            sums: Vec::new(),
+        }
+    }
+
+}
+
+impl Optimizer for Adagrad {
+
+    fn step(&mut self, parameters: Vec<&mut [f64]>, gradients: &[Vector<f64>]) {
+        init_state(&mut self.sums, &parameters);
+
+        for ((parameter, gradient), sum) in parameters.into_iter().zip(gradients).zip(&mut self.sums) {
+            for ((p, &g), s) in parameter.iter_mut().zip(gradient).zip(sum) {
+                *s += g * g;
+                *p -= self.learning_rate * g / (s.sqrt() + self.epsilon);
+            }
+        }
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
+}
@@ -0,0 +1,210 @@
+
+use std::f64::consts::PI;
+
+
+/// Chooses the learning rate for each epoch of training.
+pub trait LrScheduler {
+
+    /// The learning rate for `epoch`, counting from zero. `loss` is the loss
+    /// the previous epoch ended with, `None` before the first; only
+    /// loss-driven schedules use it.
+    fn learning_rate(&mut self, epoch: usize, loss: Option<f64>) -> f64;
+
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Constant(pub f64);
+
+impl LrScheduler for Constant {
+    fn learning_rate(&mut self, _: usize, _: Option<f64>) -> f64 {
+        self.0
+    }
+}
+
+
+/// Multiplies the rate by `gamma` every `step_size` epochs.
+#[derive(Debug, Clone)]
+pub struct StepDecay {
+    pub initial: f64,
+    pub step_size: usize,
+    pub gamma: f64,
+}
+
+impl LrScheduler for StepDecay {
+    fn learning_rate(&mut self, epoch: usize, _: Option<f64>) -> f64 {
+        self.initial * self.gamma.powi((epoch / self.step_size.max(1)) as i32)
+    }
+}
+
+
+/// Multiplies the rate by `gamma` every epoch.
+#[derive(Debug, Clone)]
+pub struct Exponential {
+    pub initial: f64,
+    pub gamma: f64,
+}
+
+impl LrScheduler for Exponential {
+    fn learning_rate(&mut self, epoch: usize, _: Option<f64>) -> f64 {
+        self.initial * self.gamma.powi(epoch as i32)
+    }
+}
+
+
+/// Cosine annealing from `max` to `min` with warm restarts (SGDR): the first
+/// cycle lasts `period` epochs and each one after is `period_multiplier`
+/// times longer than the last.
+#[derive(Debug, Clone)]
+pub struct CosineAnnealing {
+    pub max: f64,
+    pub min: f64,
+    pub period: usize,
+    pub period_multiplier: usize,
+}
+
+impl CosineAnnealing {
+
+    /// A single cycle of `period` epochs, restarting at `max` after it.
+    pub fn new(max: f64, min: f64, period: usize) -> Self {
+        CosineAnnealing {
+            max,
+            min,
+            period,
+            period_multiplier: 1,
+        }
+    }
+
+}
+
+impl LrScheduler for CosineAnnealing {
+    fn learning_rate(&mut self, epoch: usize, _: Option<f64>) -> f64 {
+        let (mut start, mut period) = (0, self.period.max(1));
+        while epoch >= start + period {
+            start += period;
+            period *= self.period_multiplier.max(1);
+        }
+
+        let progress = (epoch - start) as f64 / period as f64;
+        self.min + 0.5 * (self.max - self.min) * (1.0 + (PI * progress).cos())
+    }
+}
+
+
+/// Ramps linearly up to `schedule`'s first rate over `epochs` epochs, then
+/// follows `schedule` from its epoch zero.
+#[derive(Debug, Clone)]
+pub struct LinearWarmup<S> {
+    pub epochs: usize,
+    pub schedule: S,
+}
+
+impl<S: LrScheduler> LrScheduler for LinearWarmup<S> {
+    fn learning_rate(&mut self, epoch: usize, loss: Option<f64>) -> f64 {
+        if epoch < self.epochs {
+            let target = self.schedule.learning_rate(0, None);
+            target * (epoch + 1) as f64 / (self.epochs + 1) as f64
+        } else {
+            self.schedule.learning_rate(epoch - self.epochs, loss)
+        }
+    }
+}
+
+
+/// The one-cycle policy: cosine annealing up from `max / div_factor` to
+/// `max` over the first `warmup_fraction` of `epochs`, then down to
+/// `max / (div_factor * final_div_factor)`.
+#[derive(Debug, Clone)]
+pub struct OneCycle {
+    pub max: f64,
+    pub epochs: usize,
+    pub warmup_fraction: f64,
+    pub div_factor: f64,
+    pub final_div_factor: f64,
+}
+
+impl OneCycle {
+
+    pub fn new(max: f64, epochs: usize) -> Self {
+        OneCycle {
+            max,
+            epochs,
+            warmup_fraction: 0.3,
+            div_factor: 25.0,
+            final_div_factor: 1e4,
+        }
+    }
+
+}
+
+// cosine interpolation from `from` at `progress` 0 to `to` at 1
+fn cosine(from: f64, to: f64, progress: f64) -> f64 {
+    to + 0.5 * (from - to) * (1.0 + (PI * progress.clamp(0.0, 1.0)).cos())
+}
+
+impl LrScheduler for OneCycle {
+    fn learning_rate(&mut self, epoch: usize, _: Option<f64>) -> f64 {
+        let initial = self.max / self.div_factor;
+        let last = self.epochs.saturating_sub(1).max(1) as f64;
+        let peak = (self.warmup_fraction * last).max(1.0);
+        let epoch = epoch as f64;
+
+        if epoch < peak {
+            cosine(initial, self.max, epoch / peak)
+        } else {
+            cosine(self.max, initial / self.final_div_factor, (epoch - peak) / (last - peak).max(1.0))
+        }
+    }
+}
+
+
+/// Multiplies the rate by `factor` once the loss, typically the validation
+/// loss, has not improved by a relative `threshold` for more than `patience`
+/// epochs.
+#[derive(Debug, Clone)]
+pub struct ReduceOnPlateau {
+    pub factor: f64,
+    pub patience: usize,
+    pub threshold: f64,
+    pub min: f64,
+    rate: f64,
+    best: f64,
+    stale_epochs: usize,
+}
+
+impl ReduceOnPlateau {
+
+    pub fn new(initial: f64) -> Self {
+        ReduceOnPlateau {
+            factor: 0.1,
+            patience: 10,
+            threshold: 1e-4,
+            min: 0.0,
+            rate: initial,
+            best: f64::INFINITY,
+            stale_epochs: 0,
+        }
+    }
+
+}
+
+impl LrScheduler for ReduceOnPlateau {
+    fn learning_rate(&mut self, _: usize, loss: Option<f64>) -> f64 {
+        let Some(loss) = loss else {
+            return self.rate;
+        };
+
+        if loss < self.best * (1.0 - self.threshold) {
+            self.best = loss;
+            self.stale_epochs = 0;
+        } else {
+            self.stale_epochs += 1;
+            if self.stale_epochs > self.patience {
+                self.rate = (self.rate * self.factor).max(self.min);
+                self.stale_epochs = 0;
+            }
+        }
+
+        self.rate
+    }
+}
@@ -0,0 +1,99 @@
+use {
+    maths::{diff, Matrix, Vector},
+    neural_net::{activations::ActivationFunction, init::Initializer, Dense, Layer},
+    rand::{rngs::StdRng, SeedableRng},
+};
+
+
+// `backward` against Jᵀ g with a numeric Jacobian
+fn assert_backward(activation: ActivationFunction, z: &[f64], g: &[f64]) {
+    let (z, g) = (Vector::new(z.to_vec()), Vector::new(g.to_vec()));
+    let jacobian = diff::jacobian(|z| activation.activate_vector(z), &z);
+
+    assert!(activation.backward(&z, &g).abs_diff_eq(&(&jacobian.transpose() * &g), 1e-6), "{:?}", activation);
+}
+
+#[test]
+fn softmax() {
+
+    let s = ActivationFunction::Softmax.activate_vector(&Vector::new(vec![1.0, 2.0, 3.0]));
+    assert!((s.sum() - 1.0).abs() < 1e-12);
+    assert!(s[0] < s[1] && s[1] < s[2]);
+
+    // shifting the inputs changes nothing
+    let shifted = ActivationFunction::Softmax.activate_vector(&Vector::new(vec![101.0, 102.0, 103.0]));
+    assert!(s.iter().zip(&shifted).all(|(a, b)| (a - b).abs() < 1e-12));
+
+    // no overflow to NaN
+    let large = ActivationFunction::Softmax.activate_vector(&Vector::new(vec![1000.0, 0.0, -1000.0]));
+    assert_eq!(large.elements, vec![1.0, 0.0, 0.0]);
+
+    assert!(!ActivationFunction::Softmax.is_element_wise());
+    assert!(ActivationFunction::Tanh.is_element_wise());
+
+}
+
+#[test]
+fn log_softmax() {
+
+    let z = Vector::new(vec![0.5, -1.0, 2.0]);
+    let s = ActivationFunction::Softmax.activate_vector(&z);
+    let log_s = ActivationFunction::LogSoftmax.activate_vector(&z);
+    assert!(s.iter().zip(&log_s).all(|(p, log_p)| (p.ln() - log_p).abs() < 1e-12));
+
+    // finite where the softmax underflows to 0
+    let log_s = ActivationFunction::LogSoftmax.activate_vector(&Vector::new(vec![1000.0, 0.0]));
+    assert_eq!(log_s.elements, vec![0.0, -1000.0]);
+
+}
+
+#[test]
+fn jacobian_products() {
+
+    let z = [0.3, -1.2, 2.0, 0.7];
+    let g = [1.0, -0.5, 0.25, 2.0];
+
+    for activation in [
+        ActivationFunction::ReLU,
+        ActivationFunction::Sigmoid,
+        ActivationFunction::Tanh,
+        ActivationFunction::Softmax,
+        ActivationFunction::LogSoftmax,
+    ] {
+        assert_backward(activation, &z, &g);
+    }
+
+}
+
+#[test]
+#[should_panic]
+fn softmax_is_not_element_wise() {
+
+    ActivationFunction::Softmax.activate(1.0);
+
+}
+
+#[test]
+fn dense_softmax_backward() {
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut layer = Dense::with_initializer(3, 4, ActivationFunction::Softmax, Initializer::XavierNormal, &mut rng);
+
+    let input = Matrix::new(2, 3, vec![0.5, -1.0, 2.0, 1.5, 0.0, -0.5]);
+    let output_gradient = Matrix::new(2, 4, vec![1.0, 0.0, -2.0, 0.5, 0.0, 3.0, 1.0, -1.0]);
+
+    let output = layer.forward(&input);
+    assert!(output.rows().all(|row| (row.iter().sum::<f64>() - 1.0).abs() < 1e-12));
+
+    let gradients = layer.backward(&output_gradient);
+
+    // the Jacobian of the whole batch's output with respect to its input
+    let jacobian = diff::jacobian(
+        |x| Vector::new(layer.predict(&Matrix::new(2, 3, x.elements.clone())).elements),
+        &Vector::new(input.elements.clone()),
+    );
+    let expected = &jacobian.transpose() * &Vector::new(output_gradient.elements);
+
+    assert!(Vector::new(gradients.input.elements).abs_diff_eq(&expected, 1e-6));
+
+}
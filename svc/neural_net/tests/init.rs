@@ -0,0 +1,99 @@
+use {
+    maths::Matrix,
+    neural_net::{activations::ActivationFunction, init::Initializer, Dense, Layer},
+    rand::{rngs::StdRng, SeedableRng},
+};
+
+
+fn variance(m: &Matrix<f64>) -> f64 {
+    let n = m.elements.len() as f64;
+    let mean = m.iter().sum::<f64>() / n;
+    m.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n
+}
+
+#[test]
+fn scaled_variances() {
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let (fan_in, fan_out) = (200, 100);
+
+    let cases = [
+        (Initializer::XavierUniform, 2.0 / 300.0),
+        (Initializer::XavierNormal, 2.0 / 300.0),
+        (Initializer::HeUniform, 2.0 / 200.0),
+        (Initializer::HeNormal, 2.0 / 200.0),
+        (Initializer::LeCunUniform, 1.0 / 200.0),
+        (Initializer::LeCunNormal, 1.0 / 200.0),
+    ];
+
+    for (initializer, expected) in cases {
+        let weights = initializer.weights(fan_in, fan_out, &mut rng);
+        assert_eq!((weights.rows, weights.cols), (fan_out, fan_in));
+
+        let relative = (variance(&weights) - expected).abs() / expected;
+        assert!(relative < 0.05, "{:?}: variance {} vs {}", initializer, variance(&weights), expected);
+    }
+
+    // uniform schemes stay within their limit
+    let limit = (6.0_f64 / 300.0).sqrt();
+    assert!(Initializer::XavierUniform.weights(fan_in, fan_out, &mut rng).iter().all(|x| x.abs() <= limit));
+
+}
+
+#[test]
+fn orthogonal() {
+
+    let mut rng = StdRng::seed_from_u64(5);
+
+    for (fan_in, fan_out) in [(3, 5), (5, 3), (4, 4)] {
+        let w = Initializer::Orthogonal { gain: 2.0 }.weights(fan_in, fan_out, &mut rng);
+        assert_eq!((w.rows, w.cols), (fan_out, fan_in));
+
+        // the shorter side's Gram matrix is 4 I
+        let gram = if fan_out >= fan_in { &w.transpose() * &w } else { &w * &w.transpose() };
+        let identity = Matrix::from_fn(gram.rows, gram.cols, |i, j| if i == j { 4.0 } else { 0.0 });
+        assert!(gram.abs_diff_eq(&identity, 1e-12));
+    }
+
+}
+
+#[test]
+fn constant_schemes() {
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    assert!(Initializer::Zeros.weights(3, 2, &mut rng).iter().all(|&x| x == 0.0));
+    assert!(Initializer::Constant(0.5).weights(3, 2, &mut rng).iter().all(|&x| x == 0.5));
+
+    // degenerate bounds are a constant
+    let uniform = Initializer::Uniform { low: 0.25, high: 0.25 }.weights(3, 2, &mut rng);
+    assert!(uniform.iter().all(|&x| x == 0.25));
+    let uniform = Initializer::Uniform { low: -1.0, high: 2.0 }.weights(30, 20, &mut rng);
+    assert!(uniform.iter().all(|&x| (-1.0..=2.0).contains(&x)));
+
+}
+
+#[test]
+fn dense_defaults() {
+
+    assert_eq!(Initializer::for_activation(ActivationFunction::ReLU), Initializer::HeNormal);
+    assert_eq!(Initializer::for_activation(ActivationFunction::Tanh), Initializer::XavierUniform);
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let layer = Dense::new(4, 3, ActivationFunction::Sigmoid, &mut rng);
+    assert!(layer.biases.iter().all(|&b| b == 0.0));
+    assert_eq!(layer.output_shape(), 3);
+
+    let layer = Dense::with_initializer(4, 3, ActivationFunction::ReLU, Initializer::Constant(0.1), &mut rng);
+    assert!(layer.weights.iter().all(|&w| w == 0.1));
+
+}
+
+#[test]
+#[should_panic(expected = "low <= high")]
+fn reversed_uniform_bounds() {
+
+    let mut rng = StdRng::seed_from_u64(0);
+    Initializer::Uniform { low: 1.0, high: 0.0 }.weights(3, 2, &mut rng);
+
+}
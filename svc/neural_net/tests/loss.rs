@@ -0,0 +1,205 @@
+use {
+    maths::{diff, Matrix, Vector},
+    neural_net::{activations::ActivationFunction, loss::LossFunction, optim::Sgd, Dense, NeuralNetwork, TrainOptions},
+    rand::{rngs::StdRng, SeedableRng},
+};
+
+
+fn assert_gradient(loss: &LossFunction, output: &[f64], target: &[f64]) {
+    let (output, target) = (Vector::new(output.to_vec()), Vector::new(target.to_vec()));
+    let numeric = diff::gradient(|output| loss.loss(output, &target), &output);
+
+    assert!(loss.derivative(&output, &target).abs_diff_eq(&numeric, 1e-5), "{:?}", loss);
+}
+
+#[test]
+fn classification_gradients() {
+
+    let weights = Some(vec![0.5, 2.0, 1.0]);
+    let probabilities = [0.2, 0.7, 0.1];
+    let logits = [1.5, -0.3, 0.8];
+    let one_hot = [0.0, 1.0, 0.0];
+    let soft = [0.1, 0.6, 0.3];
+
+    for weights in [None, weights] {
+        for target in [one_hot, soft] {
+            assert_gradient(&LossFunction::CrossEntropy { weights: weights.clone() }, &probabilities, &target);
+            assert_gradient(&LossFunction::SoftmaxCrossEntropy { weights: weights.clone() }, &logits, &target);
+            assert_gradient(&LossFunction::BinaryCrossEntropy { weights: weights.clone() }, &probabilities, &target);
+            assert_gradient(&LossFunction::BinaryCrossEntropyWithLogits { weights: weights.clone() }, &logits, &target);
+            assert_gradient(&LossFunction::Focal { gamma: 2.0, weights: weights.clone() }, &probabilities, &target);
+        }
+        assert_gradient(&LossFunction::Hinge { weights: weights.clone() }, &logits, &one_hot);
+    }
+
+}
+
+#[test]
+fn known_values() {
+
+    let target = Vector::new(vec![0.0, 1.0]);
+
+    let ce = LossFunction::CrossEntropy { weights: None };
+    assert!((ce.loss(&Vector::new(vec![0.5, 0.5]), &target) - 2.0_f64.ln()).abs() < 1e-12);
+
+    // equal logits give the same softmax
+    let softmax_ce = LossFunction::SoftmaxCrossEntropy { weights: None };
+    assert!((softmax_ce.loss(&Vector::new(vec![3.0, 3.0]), &target) - 2.0_f64.ln()).abs() < 1e-12);
+
+    let bce = LossFunction::BinaryCrossEntropyWithLogits { weights: None };
+    assert!((bce.loss(&Vector::new(vec![0.0]), &Vector::new(vec![1.0])) - 2.0_f64.ln()).abs() < 1e-12);
+
+    // margins 1 + 0.5 - 2 < 0 and 1 + 1.8 - 2
+    let hinge = LossFunction::Hinge { weights: None };
+    assert!((hinge.loss(&Vector::new(vec![0.5, 2.0, 1.8]), &Vector::new(vec![0.0, 1.0, 0.0])) - 0.8).abs() < 1e-12);
+
+    // γ = 0 is cross-entropy
+    let focal = LossFunction::Focal { gamma: 0.0, weights: None };
+    let p = Vector::new(vec![0.3, 0.7]);
+    assert!((focal.loss(&p, &target) - ce.loss(&p, &target)).abs() < 1e-12);
+    assert!(focal.loss(&p, &target) > LossFunction::Focal { gamma: 2.0, weights: None }.loss(&p, &target));
+
+}
+
+#[test]
+fn focal_without_focusing_is_cross_entropy() {
+
+    let weights = Some(vec![0.5, 2.0]);
+    let focal = LossFunction::Focal { gamma: 0.0, weights: weights.clone() };
+    let ce = LossFunction::CrossEntropy { weights };
+
+    // including a certain prediction, where γ > 0 has no gradient
+    for output in [[0.3, 0.7], [0.0, 1.0]] {
+        let output = Vector::new(output.to_vec());
+        let target = Vector::new(vec![0.0, 1.0]);
+        assert_eq!(focal.derivative(&output, &target), ce.derivative(&output, &target));
+    }
+
+}
+
+#[test]
+fn class_weights_scale_the_target_class() {
+
+    let output = Vector::new(vec![0.2, 0.8]);
+    let target = Vector::new(vec![0.0, 1.0]);
+
+    let plain = LossFunction::CrossEntropy { weights: None };
+    let weighted = LossFunction::CrossEntropy { weights: Some(vec![1.0, 3.0]) };
+
+    assert!((weighted.loss(&output, &target) - 3.0 * plain.loss(&output, &target)).abs() < 1e-12);
+
+}
+
+#[test]
+#[should_panic(expected = "one weight per output")]
+fn class_weights_cover_every_output() {
+
+    let loss = LossFunction::SoftmaxCrossEntropy { weights: Some(vec![1.0, 2.0]) };
+    loss.derivative(&Vector::new(vec![0.1, 0.2, 0.3]), &Vector::new(vec![0.0, 0.0, 1.0]));
+
+}
+
+#[test]
+fn logits_forms_are_stable() {
+
+    let target = Vector::new(vec![1.0, 0.0]);
+    let logits = Vector::new(vec![1000.0, -1000.0]);
+
+    for loss in [
+        LossFunction::SoftmaxCrossEntropy { weights: None },
+        LossFunction::BinaryCrossEntropyWithLogits { weights: None },
+    ] {
+        assert!(loss.loss(&logits, &target).abs() < 1e-12);
+        assert!(loss.derivative(&logits, &target).iter().all(|g| g.abs() < 1e-12));
+
+        // confidently wrong: large but finite
+        let wrong = loss.loss(&Vector::new(vec![-1000.0, 1000.0]), &target);
+        assert!(wrong.is_finite() && wrong > 1000.0);
+    }
+
+}
+
+#[test]
+fn regression_gradients() {
+
+    // residuals away from the kinks at 0 and ±delta
+    let output = [0.3, -2.5, 1.7, 0.05];
+    let target = [1.0, 0.0, 1.5, -0.4];
+
+    for loss in [
+        LossFunction::MeanAbsoluteError,
+        LossFunction::Huber { delta: 1.0 },
+        LossFunction::Huber { delta: 0.1 },
+        LossFunction::LogCosh,
+        LossFunction::Quantile { tau: 0.9 },
+        LossFunction::Quantile { tau: 0.5 },
+    ] {
+        assert_gradient(&loss, &output, &target);
+    }
+
+}
+
+#[test]
+fn regression_values() {
+
+    let output = Vector::new(vec![1.0, 5.0]);
+    let target = Vector::new(vec![0.0, 0.0]);
+
+    assert_eq!(LossFunction::MeanAbsoluteError.loss(&output, &target), 3.0);
+
+    // 1/2 and 2 (5 - 1)
+    assert_eq!(LossFunction::Huber { delta: 2.0 }.loss(&output, &target), 4.25);
+
+    // under-predicting costs τ, over-predicting 1 - τ
+    let quantile = LossFunction::Quantile { tau: 0.9 };
+    assert!((quantile.loss(&Vector::new(vec![0.0]), &Vector::new(vec![1.0])) - 0.9).abs() < 1e-12);
+    assert!((quantile.loss(&Vector::new(vec![1.0]), &Vector::new(vec![0.0])) - 0.1).abs() < 1e-12);
+
+    // log-cosh stays finite and approaches |r| - ln 2
+    let far = LossFunction::LogCosh.loss(&Vector::new(vec![1000.0]), &target);
+    assert!((far - (1000.0 - 2.0_f64.ln())).abs() < 1e-9);
+    assert_eq!(LossFunction::LogCosh.loss(&target, &target), 0.0);
+
+    // gradients of the robust losses are bounded
+    let outlier = Vector::new(vec![1e6]);
+    let zero = Vector::new(vec![0.0]);
+    assert_eq!(LossFunction::MeanAbsoluteError.derivative(&outlier, &zero)[0], 1.0);
+    assert_eq!(LossFunction::Huber { delta: 2.0 }.derivative(&outlier, &zero)[0], 2.0);
+    assert_eq!(LossFunction::LogCosh.derivative(&outlier, &zero)[0], 1.0);
+
+}
+
+#[test]
+fn sample_weights() {
+
+    let inputs = Matrix::from_fn(8, 1, |row, _| row as f64 / 4.0 - 1.0);
+    let targets = Matrix::from_fn(8, 1, |row, _| 0.5 * inputs[(row, 0)]);
+
+    let train = |learning_rate: f64, weights: &[f64]| {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut network = NeuralNetwork::new(LossFunction::Huber { delta: 0.5 });
+        network.add_layer(Dense::new(1, 4, ActivationFunction::Tanh, &mut rng));
+        network.add_layer(Dense::new(4, 1, ActivationFunction::Tanh, &mut rng));
+
+        let options = TrainOptions {
+            epochs: 5,
+            batch_size: 3,
+            sample_weights: Some(weights),
+            ..TrainOptions::default()
+        };
+        let history = network.train_batches(&inputs, &targets, &mut Sgd::new(learning_rate), None, &options, &mut rng);
+        (history, network.predict_batch(&inputs))
+    };
+
+    // doubling every weight is doubling the learning rate, with twice the loss
+    let (doubled, doubled_outputs) = train(0.1, &[2.0; 8]);
+    let (faster, faster_outputs) = train(0.2, &[1.0; 8]);
+
+    assert!((doubled.loss[0] - 2.0 * faster.loss[0]).abs() < 1e-12);
+    assert!(doubled_outputs.abs_diff_eq(&faster_outputs, 1e-12));
+
+    // samples weighted zero contribute nothing
+    let (ignored, _) = train(0.1, &[0.0; 8]);
+    assert!(ignored.loss.iter().all(|&loss| loss == 0.0));
+
+}
@@ -1,10 +1,11 @@
 use {
-    rand::Rng,
+    rand::{rngs::StdRng, Rng, SeedableRng},
     ctor::ctor,
-    maths::Vector,
+    maths::{diff, Matrix, Vector},
     log::{debug, info},
     neural_net::{
-        activations::ActivationFunction, loss::LossFunction, Layer, NeuralNetwork
+        activations::ActivationFunction, init::Initializer, loss::LossFunction, optim::Sgd, Dense, Dropout, Gradients, Layer,
+        NeuralNetwork, TrainOptions
     },
 };
 
@@ -17,10 +18,11 @@ fn setup() {
 #[test]
 fn test_forward_propagation() {
 
+    let mut rng = StdRng::seed_from_u64(42);
     let mut network = NeuralNetwork::new(LossFunction::MeanSquaredError);
 
-    network.add_layer(Layer::new(2, 3, ActivationFunction::Sigmoid));
-    network.add_layer(Layer::new(3, 1, ActivationFunction::Sigmoid));
+    network.add_layer(Dense::new(2, 3, ActivationFunction::Sigmoid, &mut rng));
+    network.add_layer(Dense::new(3, 1, ActivationFunction::Sigmoid, &mut rng));
 
     let input = Vector::new(vec![0.5, -0.5]);
     let output = network.predict(&input);
@@ -33,10 +35,11 @@ fn test_forward_propagation() {
 #[test]
 fn test_train_xor() {
 
+    let mut rng = StdRng::seed_from_u64(42);
     let mut network = NeuralNetwork::new(LossFunction::MeanSquaredError);
 
-    network.add_layer(Layer::new(2, 2, ActivationFunction::Sigmoid));
-    network.add_layer(Layer::new(2, 1, ActivationFunction::Sigmoid));
+    network.add_layer(Dense::new(2, 2, ActivationFunction::Sigmoid, &mut rng));
+    network.add_layer(Dense::new(2, 1, ActivationFunction::Sigmoid, &mut rng));
 
     let inputs = vec![
         Vector::new(vec![0.0, 0.0]),
@@ -52,7 +55,7 @@ fn test_train_xor() {
         Vector::new(vec![0.0]),
     ];
 
-    network.train(&inputs, &targets, 0.5, 10000);
+    network.train(&inputs, &targets, &mut Sgd::new(0.5), 10000);
 
     for (input, target) in inputs.iter().zip(targets.iter()) {
         let output = network.predict(input);
@@ -65,28 +68,93 @@ fn test_train_xor() {
 
 #[test]
 fn test_layer_functionality() {
-    let layer = Layer::new(2, 2, ActivationFunction::Sigmoid);
-    let input = Vector::new(vec![0.5, 0.5]);
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut layer = Dense::new(2, 2, ActivationFunction::Sigmoid, &mut rng);
+    let input = Matrix::new(3, 2, vec![0.5, 0.5, 0.0, 1.0, -1.0, 0.25]);
 
     // forward pass
     let output = layer.forward(&input);
-    assert_eq!(output.elements.len(), 2);
+    assert_eq!((output.rows, output.cols), (3, 2));
+    assert_eq!(output, layer.predict(&input));
 
     // backward pass
-    let delta = Vector::new(vec![0.1, 0.2]);
-    let (delta_weights, delta_biases, delta_input) = layer.backward(&input, &delta);
+    let delta = Matrix::new(3, 2, vec![0.1, 0.2, 0.0, -0.1, 0.3, 0.0]);
+    let gradients = layer.backward(&delta);
+
+    assert_eq!(layer.output_shape(), 2);
+    assert_eq!(gradients.parameters.len(), 2);
+    assert_eq!(gradients.parameters[0].elements.len(), 4);
+    assert_eq!(gradients.parameters[1].elements.len(), 2);
+    assert_eq!((gradients.input.rows, gradients.input.cols), (3, 2));
+}
+
+#[test]
+fn test_backward_matches_finite_differences() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut layer = Dense::new(3, 2, ActivationFunction::Tanh, &mut rng);
+    let input = Matrix::new(2, 3, vec![0.3, -0.7, 0.9, -0.2, 0.4, 0.1]);
+    let target = Matrix::new(2, 2, vec![0.2, -0.4, 0.5, 0.0]);
+
+    // half the squared error summed over the batch, whose output gradient is `output - target`
+    let objective = |layer: &Dense, input: &Matrix<f64>| {
+        let diff = &layer.predict(input) - &target;
+        0.5 * diff.iter().map(|d| d * d).sum::<f64>()
+    };
 
-    assert_eq!(delta_weights.rows, 2);
-    assert_eq!(delta_weights.cols, 2);
-    assert_eq!(delta_biases.elements.len(), 2);
-    assert_eq!(delta_input.elements.len(), 2);
+    let output = layer.forward(&input);
+    let gradients = layer.backward(&(&output - &target));
+
+    for (k, gradient) in gradients.parameters.iter().enumerate() {
+        // the objective with parameter `k` replaced
+        let numeric = diff::gradient(|values| {
+            let mut probe = Dense::with_initializer(3, 2, ActivationFunction::Tanh, Initializer::Zeros, &mut rng.clone());
+            for (probe, original) in probe.parameters_mut().into_iter().zip(layer.parameters()) {
+                probe.copy_from_slice(original);
+            }
+            probe.parameters_mut()[k].copy_from_slice(&values.elements);
+            objective(&probe, &input)
+        }, &Vector::new(layer.parameters()[k].to_vec()));
+
+        assert!(numeric.abs_diff_eq(gradient, 1e-6));
+    }
+
+    let numeric = diff::gradient(
+        |x| objective(&layer, &Matrix::new(2, 3, x.elements.clone())),
+        &Vector::new(input.elements.clone()),
+    );
+    assert!(numeric.abs_diff_eq(&Vector::new(gradients.input.elements), 1e-6));
+}
+
+#[test]
+fn test_train_batches() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut network = NeuralNetwork::new(LossFunction::MeanSquaredError);
+    network.add_layer(Dense::new(1, 8, ActivationFunction::Tanh, &mut rng));
+    network.add_layer(Dense::new(8, 1, ActivationFunction::Tanh, &mut rng));
+
+    // y = x² / 2 on [-1, 1]
+    let inputs = Matrix::from_fn(64, 1, |row, _| row as f64 / 31.5 - 1.0);
+    let targets = Matrix::from_fn(64, 1, |row, _| 0.5 * inputs[(row, 0)].powi(2));
+
+    let options = TrainOptions {
+        epochs: 2000,
+        batch_size: 8,
+        ..TrainOptions::default()
+    };
+    let history = network.train_batches(&inputs, &targets, &mut Sgd::new(0.1), None, &options, &mut rng);
+
+    let losses = history.loss;
+    assert_eq!(losses.len(), 2000);
+    assert!(losses[1999] < 0.1 * losses[0]);
+    assert!(losses[1999] < 1e-3);
 }
 
 #[test]
 fn test_edge_case_zero_input() {
+    let mut rng = StdRng::seed_from_u64(42);
     let mut network = NeuralNetwork::new(LossFunction::MeanSquaredError);
-    network.add_layer(Layer::new(2, 2, ActivationFunction::Sigmoid));
-    network.add_layer(Layer::new(2, 1, ActivationFunction::Sigmoid));
+    network.add_layer(Dense::new(2, 2, ActivationFunction::Sigmoid, &mut rng));
+    network.add_layer(Dense::new(2, 1, ActivationFunction::Sigmoid, &mut rng));
 
     let input = Vector::new(vec![0.0, 0.0]);
     let output = network.predict(&input);
@@ -97,9 +165,10 @@ fn test_edge_case_zero_input() {
 
 #[test]
 fn test_large_input_values() {
+    let mut rng = StdRng::seed_from_u64(42);
     let mut network = NeuralNetwork::new(LossFunction::MeanSquaredError);
-    network.add_layer(Layer::new(2, 2, ActivationFunction::Sigmoid));
-    network.add_layer(Layer::new(2, 1, ActivationFunction::Sigmoid));
+    network.add_layer(Dense::new(2, 2, ActivationFunction::Sigmoid, &mut rng));
+    network.add_layer(Dense::new(2, 1, ActivationFunction::Sigmoid, &mut rng));
 
     let input = Vector::new(vec![1e6, -1e6]); // mucho grande input
     let output = network.predict(&input);
@@ -111,12 +180,13 @@ fn test_large_input_values() {
 #[test]
 fn test_train_validate_xor() {
 
+    let mut rng = StdRng::seed_from_u64(1);
     let mut network = NeuralNetwork::new(LossFunction::MeanSquaredError);
 
-    network.add_layer(Layer::new(2, 2, ActivationFunction::Sigmoid));
-    network.add_layer(Layer::new(2, 1, ActivationFunction::Sigmoid));
+    network.add_layer(Dense::new(2, 2, ActivationFunction::Sigmoid, &mut rng));
+    network.add_layer(Dense::new(2, 1, ActivationFunction::Sigmoid, &mut rng));
 
-    let inputs = vec![
+    let inputs = [
         Vector::new(vec![0.0, 0.0]),
         Vector::new(vec![0.0, 1.0]),
         Vector::new(vec![1.0, 0.0]),
@@ -126,7 +196,7 @@ fn test_train_validate_xor() {
         Vector::new(vec![0.5, 0.0]),
     ];
 
-    let targets = vec![
+    let targets = [
         Vector::new(vec![0.0]),
         Vector::new(vec![1.0]),
         Vector::new(vec![1.0]),
@@ -136,10 +206,11 @@ fn test_train_validate_xor() {
         Vector::new(vec![1.0]),
     ];
 
+    let mut optimizer = Sgd::new(0.01);
     // train
     for epoch in 0..100_000 {
         for (input, target) in inputs.iter().zip(targets.iter()) {
-            network.train(&[input.clone()], &[target.clone()], 0.01, 1);
+            network.train(std::slice::from_ref(input), std::slice::from_ref(target), &mut optimizer, 1);
 
             if epoch % 1000 == 0 {
                 let loss: f64 = inputs.iter()
@@ -166,11 +237,11 @@ fn test_train_validate_xor() {
 
 #[test]
 fn test_random_inputs() {
+    let mut rng = StdRng::seed_from_u64(42);
     let mut network = NeuralNetwork::new(LossFunction::MeanSquaredError);
-    network.add_layer(Layer::new(2, 2, ActivationFunction::Sigmoid));
-    network.add_layer(Layer::new(2, 1, ActivationFunction::Sigmoid));
+    network.add_layer(Dense::new(2, 2, ActivationFunction::Sigmoid, &mut rng));
+    network.add_layer(Dense::new(2, 1, ActivationFunction::Sigmoid, &mut rng));
 
-    let mut rng = rand::thread_rng();
     let inputs: Vec<Vector<f64>> = (0..100).map(|_| {
         Vector::new(vec![rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0)])
     }).collect();
@@ -180,10 +251,11 @@ fn test_random_inputs() {
         Vector::new(vec![rng.gen_range(0.0..1.0)])
     }).collect();
 
+    let mut optimizer = Sgd::new(0.01);
     // treiiin
     for epoch in 0..42_000 {
         for (input, target) in inputs.iter().zip(targets.iter()) {
-            network.train(&[input.clone()], &[target.clone()], 0.01, 1);
+            network.train(std::slice::from_ref(input), std::slice::from_ref(target), &mut optimizer, 1);
 
             if epoch % 1000 == 0 {
                 let loss: f64 = inputs.iter()
@@ -207,28 +279,30 @@ fn test_random_inputs() {
 
 #[test]
 fn test_performance_on_noise() {
+    let mut rng = StdRng::seed_from_u64(1);
     let mut network = NeuralNetwork::new(LossFunction::MeanSquaredError);
-    network.add_layer(Layer::new(2, 2, ActivationFunction::Sigmoid));
-    network.add_layer(Layer::new(2, 1, ActivationFunction::Sigmoid));
+    network.add_layer(Dense::new(2, 2, ActivationFunction::Sigmoid, &mut rng));
+    network.add_layer(Dense::new(2, 1, ActivationFunction::Sigmoid, &mut rng));
 
-    let inputs = vec![
+    let inputs = [
         Vector::new(vec![0.0, 0.0]),
         Vector::new(vec![0.0, 1.0]),
         Vector::new(vec![1.0, 0.0]),
         Vector::new(vec![1.0, 1.0]),
     ];
 
-    let targets = vec![
+    let targets = [
         Vector::new(vec![0.0]),
         Vector::new(vec![1.0]),
         Vector::new(vec![1.0]),
         Vector::new(vec![0.0]),
     ];
 
+    let mut optimizer = Sgd::new(0.01);
     // train
     for epoch in 0..100_000 {
         for (input, target) in inputs.iter().zip(targets.iter()) {
-            network.train(&[input.clone()], &[target.clone()], 0.01, 1);
+            network.train(std::slice::from_ref(input), std::slice::from_ref(target), &mut optimizer, 1);
 
             if epoch % 1000 == 0 {
                 let loss: f64 = inputs.iter()
@@ -244,8 +318,8 @@ fn test_performance_on_noise() {
     let noise_factor = 0.1;
     for (i, t) in inputs.iter().zip(targets.iter()) {
         let noisy_input = Vector::new(vec![
-            i.elements[0] + noise_factor * (rand::random::<f64>() - 0.5),
-            i.elements[1] + noise_factor * (rand::random::<f64>() - 0.5),
+            i.elements[0] + noise_factor * (rng.gen::<f64>() - 0.5),
+            i.elements[1] + noise_factor * (rng.gen::<f64>() - 0.5),
         ]);
         let output = network.predict(&noisy_input);
         let predicted = if output.elements[0] > 0.5 { 1.0 } else { 0.0 };
@@ -270,3 +344,104 @@ fn test_performance_on_noise() {
 
 
 
+
+
+// adds a learned offset to every sample
+struct Shift {
+    offset: Vector<f64>,
+}
+
+impl Layer for Shift {
+    fn predict(&self, input: &Matrix<f64>) -> Matrix<f64> {
+        Matrix::from_fn(input.rows, input.cols, |row, col| input[(row, col)] + self.offset[col])
+    }
+
+    fn forward(&mut self, input: &Matrix<f64>) -> Matrix<f64> {
+        self.predict(input)
+    }
+
+    fn backward(&mut self, output_gradient: &Matrix<f64>) -> Gradients {
+        Gradients {
+            parameters: vec![output_gradient.cols().map(|col| col.sum()).collect()],
+            input: output_gradient.clone(),
+        }
+    }
+
+    fn parameters(&self) -> Vec<&[f64]> {
+        vec![&self.offset.elements]
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut [f64]> {
+        vec![&mut self.offset.elements]
+    }
+
+    fn output_shape(&self) -> usize {
+        self.offset.len()
+    }
+}
+
+#[test]
+fn test_custom_layer() {
+    let mut network = NeuralNetwork::new(LossFunction::MeanSquaredError);
+    network.add_layer(Shift { offset: Vector::new(vec![0.0, 0.0]) });
+
+    let inputs = [Vector::new(vec![1.0, 2.0]), Vector::new(vec![-1.0, 0.5])];
+    let targets = [Vector::new(vec![4.0, 1.0]), Vector::new(vec![2.0, -0.5])];
+
+    network.train(&inputs, &targets, &mut Sgd::new(0.1), 200);
+
+    let offset = network.layers[0].parameters()[0].to_vec();
+    assert!((offset[0] - 3.0).abs() < 1e-6 && (offset[1] + 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_seeded_runs_are_identical() {
+
+    let inputs = Matrix::from_fn(32, 2, |row, col| ((row * 7 + col * 3) % 11) as f64 / 10.0);
+    let targets = Matrix::from_fn(32, 1, |row, _| inputs[(row, 0)] * inputs[(row, 1)]);
+
+    let run = |seed: u64| {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut network = NeuralNetwork::new(LossFunction::MeanSquaredError);
+        network.add_layer(Dense::new(2, 8, ActivationFunction::ReLU, &mut rng));
+        network.add_layer(Dropout::new(8, 0.25, StdRng::seed_from_u64(rng.gen())));
+        network.add_layer(Dense::new(8, 1, ActivationFunction::Sigmoid, &mut rng));
+
+        let options = TrainOptions {
+            epochs: 20,
+            batch_size: 5,
+            ..TrainOptions::default()
+        };
+        network.train_batches(&inputs, &targets, &mut Sgd::momentum(0.1, 0.9), None, &options, &mut rng);
+
+        network.layers
+            .iter()
+            .flat_map(|layer| layer.parameters().concat())
+            .map(f64::to_bits)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(run(9), run(9));
+    assert_ne!(run(9), run(10));
+
+}
+
+#[test]
+fn test_dropout() {
+
+    let mut dropout = Dropout::new(4, 0.5, StdRng::seed_from_u64(2));
+    let input = Matrix::from_fn(100, 4, |_, _| 1.0);
+
+    assert_eq!(dropout.predict(&input), input);
+
+    let output = dropout.forward(&input);
+    let kept = output.iter().filter(|&&x| x != 0.0).count();
+    assert!(output.iter().all(|&x| x == 0.0 || x == 2.0));
+    assert!((150..250).contains(&kept));
+
+    // gradients flow only through the kept inputs, scaled the same way
+    let gradients = dropout.backward(&input);
+    assert_eq!(gradients.input, output);
+    assert!(gradients.parameters.is_empty());
+
+}
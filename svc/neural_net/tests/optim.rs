@@ -0,0 +1,124 @@
+use {
+    maths::{Matrix, Vector},
+    neural_net::{
+        activations::ActivationFunction,
+        loss::LossFunction,
+        optim::{Adagrad, Adam, AdamW, Optimizer, RmsProp, Sgd},
+        Dense,
+        NeuralNetwork,
+        TrainOptions,
+    },
+    rand::{rngs::StdRng, SeedableRng},
+};
+
+
+// minimises (x - 3)² + 10 (y + 1)² from the origin
+fn minimise(optimizer: &mut impl Optimizer, steps: usize) -> Vec<f64> {
+    let mut x = vec![0.0, 0.0];
+
+    for _ in 0..steps {
+        let gradient = Vector::new(vec![2.0 * (x[0] - 3.0), 20.0 * (x[1] + 1.0)]);
+        optimizer.step(vec![&mut x], &[gradient]);
+    }
+
+    x
+}
+
+fn assert_near(x: &[f64], expected: &[f64], tolerance: f64) {
+    for (a, b) in x.iter().zip(expected) {
+        assert!((a - b).abs() < tolerance, "{:?} is not within {} of {:?}", x, tolerance, expected);
+    }
+}
+
+#[test]
+fn sgd_momentum_steps() {
+
+    let mut sgd = Sgd::momentum(0.1, 0.9);
+    let mut p = vec![1.0];
+
+    // velocity 1, then 0.9 + 1
+    sgd.step(vec![&mut p], &[Vector::new(vec![1.0])]);
+    assert_near(&p, &[0.9], 1e-12);
+    sgd.step(vec![&mut p], &[Vector::new(vec![1.0])]);
+    assert_near(&p, &[0.71], 1e-12);
+
+    // looks ahead along the velocity: g + 0.9 v
+    let mut nesterov = Sgd::nesterov(0.1, 0.9);
+    let mut p = vec![1.0];
+    nesterov.step(vec![&mut p], &[Vector::new(vec![1.0])]);
+    assert_near(&p, &[0.81], 1e-12);
+
+}
+
+#[test]
+fn adam_first_step_is_learning_rate() {
+
+    // bias correction makes the first step `lr * sign(g)`
+    let mut adam = Adam::new(0.01);
+    let mut p = vec![0.0, 0.0];
+    adam.step(vec![&mut p], &[Vector::new(vec![5.0, -0.001])]);
+
+    assert_near(&p, &[-0.01, 0.01], 1e-6);
+
+}
+
+#[test]
+fn adamw_decays_without_gradient() {
+
+    let mut adamw = AdamW::new(0.1, 0.5);
+    let mut p = vec![2.0];
+    adamw.step(vec![&mut p], &[Vector::new(vec![0.0])]);
+
+    assert_near(&p, &[2.0 * 0.95], 1e-12);
+
+}
+
+#[test]
+#[should_panic(expected = "Parameter shapes changed")]
+fn state_matches_every_parameter() {
+
+    let mut adam = Adam::new(0.01);
+    adam.step(vec![&mut [0.0, 0.0]], &[Vector::new(vec![1.0, 1.0])]);
+
+    // same number of parameters, but a different length
+    adam.step(vec![&mut [0.0, 0.0, 0.0]], &[Vector::new(vec![1.0, 1.0, 1.0])]);
+
+}
+
+#[test]
+fn optimizers_converge() {
+
+    let minimum = [3.0, -1.0];
+
+    assert_near(&minimise(&mut Sgd::new(0.04), 500), &minimum, 1e-6);
+    assert_near(&minimise(&mut Sgd::momentum(0.01, 0.9), 500), &minimum, 1e-6);
+    assert_near(&minimise(&mut Sgd::nesterov(0.01, 0.9), 500), &minimum, 1e-6);
+    assert_near(&minimise(&mut Adam::new(0.05), 2000), &minimum, 1e-3);
+    assert_near(&minimise(&mut AdamW::new(0.05, 0.0), 2000), &minimum, 1e-3);
+    assert_near(&minimise(&mut RmsProp::new(0.01), 2000), &minimum, 1e-2);
+    assert_near(&minimise(&mut Adagrad::new(0.5), 2000), &minimum, 1e-3);
+
+}
+
+#[test]
+fn train_with_adam() {
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut network = NeuralNetwork::new(LossFunction::MeanSquaredError);
+    network.add_layer(Dense::new(1, 8, ActivationFunction::Tanh, &mut rng));
+    network.add_layer(Dense::new(8, 1, ActivationFunction::Tanh, &mut rng));
+
+    // y = x² / 2 on [-1, 1]
+    let inputs = Matrix::from_fn(64, 1, |row, _| row as f64 / 31.5 - 1.0);
+    let targets = Matrix::from_fn(64, 1, |row, _| 0.5 * inputs[(row, 0)].powi(2));
+
+    let options = TrainOptions {
+        epochs: 300,
+        batch_size: 16,
+        ..TrainOptions::default()
+    };
+    let history = network.train_batches(&inputs, &targets, &mut Adam::new(0.01), None, &options, &mut rng);
+
+    assert!(history.loss[299] < 1e-3);
+
+}
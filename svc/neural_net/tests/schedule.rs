@@ -0,0 +1,117 @@
+use {
+    maths::Matrix,
+    neural_net::{
+        activations::ActivationFunction,
+        loss::LossFunction,
+        optim::Sgd,
+        schedule::{Constant, CosineAnnealing, Exponential, LinearWarmup, LrScheduler, OneCycle, ReduceOnPlateau, StepDecay},
+        Dense,
+        NeuralNetwork,
+        TrainOptions,
+    },
+    rand::{rngs::StdRng, SeedableRng},
+};
+
+
+fn rates(scheduler: &mut impl LrScheduler, epochs: usize) -> Vec<f64> {
+    (0..epochs).map(|epoch| scheduler.learning_rate(epoch, None)).collect()
+}
+
+fn assert_near(x: &[f64], expected: &[f64]) {
+    assert_eq!(x.len(), expected.len());
+    for (a, b) in x.iter().zip(expected) {
+        assert!((a - b).abs() < 1e-12, "{:?} != {:?}", x, expected);
+    }
+}
+
+#[test]
+fn decays() {
+
+    let mut step = StepDecay { initial: 1.0, step_size: 2, gamma: 0.5 };
+    assert_near(&rates(&mut step, 5), &[1.0, 1.0, 0.5, 0.5, 0.25]);
+
+    let mut exponential = Exponential { initial: 2.0, gamma: 0.1 };
+    assert_near(&rates(&mut exponential, 3), &[2.0, 0.2, 0.02]);
+
+}
+
+#[test]
+fn cosine_warm_restarts() {
+
+    let mut cosine = CosineAnnealing {
+        period_multiplier: 2,
+        ..CosineAnnealing::new(1.0, 0.0, 2)
+    };
+
+    // cycles of 2 then 4 epochs, each restarting at the maximum
+    assert_near(&rates(&mut cosine, 7), &[1.0, 0.5, 1.0, 0.8535533905932737, 0.5, 0.14644660940672627, 1.0]);
+
+}
+
+#[test]
+fn warmup_then_schedule() {
+
+    let mut warmup = LinearWarmup {
+        epochs: 3,
+        schedule: Exponential { initial: 0.4, gamma: 0.5 },
+    };
+
+    assert_near(&rates(&mut warmup, 5), &[0.1, 0.2, 0.3, 0.4, 0.2]);
+
+}
+
+#[test]
+fn one_cycle() {
+
+    let mut one_cycle = OneCycle::new(1.0, 11);
+    let rates = rates(&mut one_cycle, 11);
+
+    assert!((rates[0] - 0.04).abs() < 1e-12);
+    assert!((rates[3] - 1.0).abs() < 1e-12);
+    assert!((rates[10] - 0.04e-4).abs() < 1e-12);
+    assert!(rates[..4].windows(2).all(|w| w[0] < w[1]));
+    assert!(rates[3..].windows(2).all(|w| w[0] > w[1]));
+
+}
+
+#[test]
+fn reduce_on_plateau() {
+
+    let mut plateau = ReduceOnPlateau::new(1.0);
+    plateau.patience = 1;
+    plateau.factor = 0.5;
+
+    let losses = [None, Some(1.0), Some(0.5), Some(0.5), Some(0.5), Some(0.6), Some(0.6), Some(0.1)];
+    let rates: Vec<f64> = losses.iter().enumerate().map(|(epoch, &loss)| plateau.learning_rate(epoch, loss)).collect();
+
+    assert_near(&rates, &[1.0, 1.0, 1.0, 1.0, 0.5, 0.5, 0.25, 0.25]);
+
+}
+
+#[test]
+fn scheduled_training() {
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut network = NeuralNetwork::new(LossFunction::MeanSquaredError);
+    network.add_layer(Dense::new(1, 4, ActivationFunction::Tanh, &mut rng));
+    network.add_layer(Dense::new(4, 1, ActivationFunction::Tanh, &mut rng));
+
+    let inputs = Matrix::from_fn(16, 1, |row, _| row as f64 / 7.5 - 1.0);
+    let targets = Matrix::from_fn(16, 1, |row, _| 0.5 * inputs[(row, 0)]);
+
+    let options = TrainOptions {
+        epochs: 4,
+        batch_size: 4,
+        validation: Some((&inputs, &targets)),
+        ..TrainOptions::default()
+    };
+
+    let mut scheduler = LinearWarmup { epochs: 1, schedule: Constant(0.2) };
+    let mut optimizer = Sgd::new(1.0);
+    let history = network.train_batches(&inputs, &targets, &mut optimizer, Some(&mut scheduler), &options, &mut rng);
+
+    assert_near(&history.learning_rates, &[0.1, 0.2, 0.2, 0.2]);
+    assert_eq!(history.validation_loss.len(), 4);
+    assert!((history.validation_loss[3] - network.evaluate(&inputs, &targets)).abs() < 1e-12);
+
+}